@@ -0,0 +1,40 @@
+//! Simple benchmark comparing deriving many sibling account keys from the seed
+//! with deriving them from a cached parent key. Run with `cargo bench`.
+
+use hdwallet::{hdk, mnemonic::Mnemonic};
+use std::{hint::black_box, time::Instant};
+
+const ACCOUNTS: u32 = 1000;
+
+fn main() {
+    let seed = "myth like bonus scare over problem client lizard pioneer submit female collect"
+        .parse::<Mnemonic>()
+        .unwrap()
+        .seed("");
+
+    let start = Instant::now();
+    for index in 0..ACCOUNTS {
+        black_box(hdk::derive_index(&seed, index as _).unwrap().address());
+    }
+    let uncached = start.elapsed();
+
+    let start = Instant::now();
+    let derivation = hdk::Derivation::new(&seed).unwrap();
+    for index in 0..ACCOUNTS {
+        black_box(derivation.child(index).unwrap().address());
+    }
+    let cached = start.elapsed();
+
+    println!(
+        "derive_index      {ACCOUNTS} accounts: {uncached:>10.1?} ({:>8.1?}/account)",
+        uncached / ACCOUNTS,
+    );
+    println!(
+        "Derivation::child {ACCOUNTS} accounts: {cached:>10.1?} ({:>8.1?}/account)",
+        cached / ACCOUNTS,
+    );
+    println!(
+        "speedup: {:.1}x",
+        uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+}
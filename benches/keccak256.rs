@@ -0,0 +1,24 @@
+//! Simple benchmark for measuring Keccak-256 hashing throughput with the
+//! selected backend. Run with `cargo bench` and compare against
+//! `cargo bench --features tiny-keccak`.
+
+use hdwallet::hash;
+use std::{hint::black_box, time::Instant};
+
+fn main() {
+    for (size, iterations) in [(32, 1_000_000), (1024, 100_000), (1024 * 1024, 100)] {
+        let data = vec![0x5a; size];
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            black_box(hash::keccak256(black_box(&data)));
+        }
+        let elapsed = start.elapsed();
+
+        let throughput = (size * iterations) as f64 / elapsed.as_secs_f64() / 1e6;
+        println!(
+            "keccak256 {size:>8} bytes: {:>10.1?}/hash, {throughput:>8.1} MB/s",
+            elapsed / iterations as u32,
+        );
+    }
+}
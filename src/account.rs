@@ -1,18 +1,41 @@
 //! Module implementing `secp256k1` private key.
 
+pub mod keystore;
 mod public;
 mod signature;
 
 pub use self::{public::PublicKey, signature::Signature};
-use crate::hash;
+use crate::{
+    hash::{self, Hash32},
+    transaction::rlp,
+};
 use anyhow::Result;
 use ethaddr::Address;
+use ethnum::U256;
 use k256::{
     ecdsa::{hazmat::SignPrimitive, SigningKey},
     SecretKey,
 };
 use sha2::Sha256;
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    str::FromStr,
+};
+
+/// Computes the address of a contract deployed with `CREATE` by the sender
+/// account with the specified nonce.
+pub fn contract_address(sender: Address, nonce: U256) -> Address {
+    let encoded = rlp::list(&[&rlp::bytes(&*sender), &rlp::uint(nonce)]);
+    let hash = hash::keccak256(encoded);
+    Address::from_slice(&hash[12..])
+}
+
+/// Computes the address of a contract deployed with `CREATE2` (see EIP-1014)
+/// by the deployer with the specified salt and hash of the init code.
+pub fn create2_address(deployer: Address, salt: Hash32, init_code_hash: Hash32) -> Address {
+    let hash = hash::keccak256_parts(&[b"\xff", &*deployer, &*salt, &*init_code_hash]);
+    Address::from_slice(&hash[12..])
+}
 
 /// A struct representing an Ethereum private key.
 pub struct PrivateKey(SecretKey);
@@ -31,18 +54,7 @@ impl PrivateKey {
 
     /// Returns the public address for the private key.
     pub fn address(&self) -> Address {
-        let encoded = self.public().encode_uncompressed();
-
-        // NOTE: An ethereum address is the last 20 bytes of the keccak hash of
-        // the concatenated elliptic curve coordinates of the public key. Note
-        // that an encoded uncompressed public key is serialized into 65 bytes
-        // where the first byte is a SEC1 tag that is always 0x04 (representing
-        // an uncompressed point) and the subsequent bytes are the coordinates
-        // we want. So discard the first byte for the address calculation.
-        debug_assert_eq!(encoded[0], 0x04);
-        let hash = hash::keccak256(&encoded[1..]);
-
-        Address::from_slice(&hash[12..])
+        self.public().address()
     }
 
     /// Returns the private key's 32 byte secret.
@@ -51,16 +63,64 @@ impl PrivateKey {
     }
 
     /// Generate a signature for the specified message.
-    pub fn sign(&self, message: [u8; 32]) -> Signature {
+    pub fn sign(&self, message: impl Into<Hash32>) -> Signature {
         self.try_sign(message).expect("signature operation failed")
     }
 
     /// Generate a signature for the specified message.
-    pub fn try_sign(&self, message: [u8; 32]) -> Result<Signature> {
+    ///
+    /// The produced signature is always normalized to have a low S-value, as
+    /// required by Ethereum (see EIP-2).
+    pub fn try_sign(&self, message: impl Into<Hash32>) -> Result<Signature> {
+        self.try_sign_with_additional_data(message, b"")
+    }
+
+    /// Generate a domain separated signature for the specified message.
+    ///
+    /// The SHA-256 hash of the domain is used as additional data for the
+    /// RFC6979 nonce derivation, meaning that signatures are deterministic for
+    /// a given domain, but differ between domains. Note that this diverges
+    /// from standard Ethereum signing, so signatures will not match the ones
+    /// produced by other wallets. They are, however, still perfectly valid
+    /// signatures for the message and recover to the same address.
+    pub fn sign_with_domain(&self, message: impl Into<Hash32>, domain: &str) -> Signature {
+        self.try_sign_with_additional_data(message, &*hash::sha256(domain))
+            .expect("signature operation failed")
+    }
+
+    /// Generate a signature for the specified message, mixing in extra entropy
+    /// for the RFC6979 nonce derivation as defense-in-depth against fault
+    /// attacks. The produced signature is still valid and recovers to the same
+    /// address, and is identical to [`PrivateKey::sign`] for empty entropy.
+    pub fn sign_with_entropy(&self, message: impl Into<Hash32>, entropy: &[u8]) -> Signature {
+        self.try_sign_with_additional_data(message, entropy)
+            .expect("signature operation failed")
+    }
+
+    /// Generate a signature for the specified message, using the additional
+    /// data for the RFC6979 nonce derivation.
+    fn try_sign_with_additional_data(
+        &self,
+        message: impl Into<Hash32>,
+        additional_data: &[u8],
+    ) -> Result<Signature> {
+        let Hash32(message) = message.into();
         let (signature, recovery_id) = SigningKey::from(&self.0)
             .as_nonzero_scalar()
-            .try_sign_prehashed_rfc6979::<Sha256>(&message.into(), b"")?;
-        Ok(Signature(signature, recovery_id.unwrap()))
+            .try_sign_prehashed_rfc6979::<Sha256>(&message.into(), additional_data)?;
+        let mut signature = Signature(signature, recovery_id.unwrap());
+        signature.normalize_s();
+        Ok(signature)
+    }
+}
+
+impl FromStr for PrivateKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut secret = [0; 32];
+        hex::decode_to_slice(s.strip_prefix("0x").unwrap_or(s), &mut secret)?;
+        Self::new(secret)
     }
 }
 
@@ -85,6 +145,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_contract_addresses() {
+        let sender = Address(hex!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"));
+        for (nonce, address) in [
+            (0, hex!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")),
+            (1, hex!("343c43a37d37dff08ae8c4a11544c718abb4fcf8")),
+        ] {
+            assert_eq!(*contract_address(sender, U256::new(nonce)), address);
+        }
+    }
+
+    #[test]
+    fn create2_contract_addresses() {
+        // Examples from EIP-1014.
+        let init_code_hash = hash::keccak256([0x00]);
+        for (deployer, address) in [
+            (
+                hex!("0000000000000000000000000000000000000000"),
+                hex!("4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38"),
+            ),
+            (
+                hex!("deadbeef00000000000000000000000000000000"),
+                hex!("b928f69bb1d91cd65274e3c79d8986362984fda3"),
+            ),
+        ] {
+            assert_eq!(
+                *create2_address(Address(deployer), Hash32([0; 32]), init_code_hash),
+                address,
+            );
+        }
+    }
+
+    #[test]
+    fn private_key_from_str() {
+        let hex = "4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d";
+        for s in [hex.to_owned(), format!("0x{hex}")] {
+            assert_eq!(
+                s.parse::<PrivateKey>().unwrap().secret(),
+                DETERMINISTIC_PRIVATE_KEY,
+            );
+        }
+
+        for invalid in [
+            "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b",
+            "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d00",
+            "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23bxx",
+        ] {
+            assert!(invalid.parse::<PrivateKey>().is_err());
+        }
+    }
+
     #[test]
     fn ganache_deterministic_signature() {
         let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
@@ -98,4 +211,48 @@ mod tests {
             ),
         );
     }
+
+    #[test]
+    fn domain_separated_signatures() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let message = hash::keccak256(b"\x19Ethereum Signed Message:\n12Hello World!");
+
+        let foo = key.sign_with_domain(message, "foo");
+        let bar = key.sign_with_domain(message, "bar");
+        assert_eq!(foo, key.sign_with_domain(message, "foo"));
+        assert_ne!(foo, bar);
+        assert_ne!(foo, key.sign(message));
+
+        for signature in [foo, bar] {
+            assert!(signature.is_low_s());
+            assert_eq!(signature.recover_address(message).unwrap(), key.address(),);
+        }
+    }
+
+    #[test]
+    fn signatures_with_entropy() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let message = hash::keccak256(b"\x19Ethereum Signed Message:\n12Hello World!");
+
+        assert_eq!(key.sign_with_entropy(message, b""), key.sign(message));
+
+        let signature = key.sign_with_entropy(message, &[42; 32]);
+        assert_ne!(signature, key.sign(message));
+        assert_ne!(signature, key.sign_with_entropy(message, &[1; 32]));
+        assert!(signature.is_low_s());
+        assert_eq!(signature.recover_address(message).unwrap(), key.address());
+    }
+
+    #[test]
+    fn signatures_have_low_s() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        for i in 0..32_u8 {
+            let signature = key.sign(hash::keccak256([i]));
+            assert!(signature.is_low_s());
+            assert_eq!(
+                signature.recover_address(hash::keccak256([i])).unwrap(),
+                key.address(),
+            );
+        }
+    }
 }
@@ -0,0 +1,461 @@
+//! Module implementing encrypted JSON keystores as specified by the Web3 Secret
+//! Storage Definition (version 3).
+//!
+//! <https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/>
+
+use crate::{account::PrivateKey, hash, rand, serialization};
+use aes::{
+    cipher::{KeyIvInit as _, StreamCipher as _},
+    Aes128,
+};
+use anyhow::{anyhow, bail, ensure, Context as _, Result};
+use hmac::Hmac;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use subtle::ConstantTimeEq as _;
+
+/// AES-128 in CTR mode with a 128-bit big-endian counter, as used by
+/// keystores with the `aes-128-ctr` cipher.
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+/// The maximum scrypt memory cost (`128 * n * r` bytes). This prevents
+/// keystores with malicious KDF parameters from exhausting memory.
+const MAX_SCRYPT_MEMORY: u128 = 1 << 30;
+
+/// The maximum scrypt parallelization parameter.
+const MAX_SCRYPT_P: u32 = 16;
+
+/// The maximum PBKDF2 iteration count. This is well above the 262144
+/// iterations used by Geth, while preventing keystores with malicious KDF
+/// parameters from hanging decryption.
+const MAX_PBKDF2_C: u32 = 10_000_000;
+
+/// The scrypt key derivation function parameters for a keystore.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct KdfParams {
+    /// The CPU/memory cost parameter. This must be a power of two.
+    pub n: u32,
+    /// The block size parameter.
+    pub r: u32,
+    /// The parallelization parameter.
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    /// Returns the scrypt parameters used by default by Geth and MetaMask.
+    fn default() -> Self {
+        Self {
+            n: 1 << 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Derives a 32-byte key from the password and salt.
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+        ensure!(
+            self.n.is_power_of_two() && self.n > 1,
+            "scrypt parameter n must be a power of two greater than 1",
+        );
+        ensure!(
+            128 * u128::from(self.n) * u128::from(self.r) <= MAX_SCRYPT_MEMORY,
+            "scrypt parameters n={} and r={} exceed the maximum memory cost",
+            self.n,
+            self.r,
+        );
+        ensure!(
+            (1..=MAX_SCRYPT_P).contains(&self.p),
+            "scrypt parameter p must be between 1 and {MAX_SCRYPT_P}",
+        );
+        let params = scrypt::Params::new(self.n.trailing_zeros() as _, self.r, self.p, 32)
+            .map_err(|err| anyhow!("invalid scrypt parameters: {err}"))?;
+
+        let mut key = [0; 32];
+        scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+            .map_err(|err| anyhow!("scrypt key derivation failed: {err}"))?;
+        Ok(key)
+    }
+}
+
+/// Decrypts a private key from a JSON keystore with a password.
+///
+/// Both the `scrypt` and `pbkdf2` key derivation functions are supported with
+/// the `aes-128-ctr` cipher. The keystore MAC is verified before decrypting, so
+/// an incorrect password results in an error instead of a wrong key.
+pub fn decrypt(json: &str, password: &str) -> Result<PrivateKey> {
+    let keystore = serde_json::from_str::<Keystore>(json).context("invalid JSON keystore")?;
+    ensure!(
+        keystore.version == 3,
+        "unsupported keystore version {}",
+        keystore.version,
+    );
+
+    let crypto = keystore.crypto;
+    ensure!(
+        crypto.cipher == "aes-128-ctr",
+        "unsupported keystore cipher '{}'",
+        crypto.cipher,
+    );
+    let derived_key = match crypto.kdf.as_str() {
+        "scrypt" => {
+            let params = serde_json::from_value::<ScryptParams>(crypto.kdfparams)?;
+            ensure!(
+                params.dklen == 32,
+                "unsupported scrypt dklen {}",
+                params.dklen
+            );
+            KdfParams {
+                n: params.n,
+                r: params.r,
+                p: params.p,
+            }
+            .derive_key(password, &params.salt)?
+        }
+        "pbkdf2" => {
+            let params = serde_json::from_value::<Pbkdf2Params>(crypto.kdfparams)?;
+            ensure!(
+                params.dklen == 32,
+                "unsupported pbkdf2 dklen {}",
+                params.dklen
+            );
+            ensure!(
+                params.prf == "hmac-sha256",
+                "unsupported pbkdf2 PRF '{}'",
+                params.prf,
+            );
+            ensure!(
+                (1..=MAX_PBKDF2_C).contains(&params.c),
+                "pbkdf2 iteration count must be between 1 and {MAX_PBKDF2_C}",
+            );
+            let mut key = [0; 32];
+            pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), &params.salt, params.c, &mut key)
+                .map_err(|err| anyhow!("pbkdf2 key derivation failed: {err}"))?;
+            key
+        }
+        kdf => bail!("unsupported keystore KDF '{kdf}'"),
+    };
+
+    let mac = hash::keccak256([&derived_key[16..], &crypto.ciphertext[..]].concat());
+    ensure!(
+        bool::from(mac[..].ct_eq(&crypto.mac)),
+        "keystore MAC mismatch, the password is incorrect",
+    );
+
+    let iv = <[u8; 16]>::try_from(&crypto.cipherparams.iv[..]).context("invalid keystore IV")?;
+    let mut secret = crypto.ciphertext;
+    Aes128Ctr::new(derived_key[..16].into(), &iv.into()).apply_keystream(&mut secret);
+    PrivateKey::new(secret)
+}
+
+/// Encrypts a private key with a password, returning the JSON keystore.
+///
+/// The keystore uses the scrypt key derivation function with the specified
+/// parameters and the `aes-128-ctr` cipher, with a random salt and IV.
+pub fn encrypt(key: &PrivateKey, password: &str, params: KdfParams) -> Result<String> {
+    let mut salt = [0; 32];
+    rand::get_entropy(&mut salt)?;
+    let mut iv = [0; 16];
+    rand::get_entropy(&mut iv)?;
+    let mut id = [0; 16];
+    rand::get_entropy(&mut id)?;
+
+    encrypt_with(key, password, params, salt, iv, id)
+}
+
+/// Encrypts a private key with the specified salt, IV and UUID bytes.
+fn encrypt_with(
+    key: &PrivateKey,
+    password: &str,
+    params: KdfParams,
+    salt: [u8; 32],
+    iv: [u8; 16],
+    id: [u8; 16],
+) -> Result<String> {
+    let derived_key = params.derive_key(password, &salt)?;
+
+    let mut ciphertext = key.secret();
+    Aes128Ctr::new(derived_key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+    let mac = hash::keccak256([&derived_key[16..], &ciphertext[..]].concat());
+
+    let keystore = json!({
+        "version": 3,
+        "id": uuid_v4(id),
+        "address": hex::encode(*key.address()),
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {
+                "iv": hex::encode(iv),
+            },
+            "ciphertext": hex::encode(ciphertext),
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": params.n,
+                "r": params.r,
+                "p": params.p,
+                "salt": hex::encode(salt),
+            },
+            "mac": hex::encode(mac),
+        },
+    });
+    Ok(keystore.to_string())
+}
+
+/// A JSON keystore.
+#[derive(Deserialize)]
+struct Keystore {
+    version: u32,
+    #[serde(alias = "Crypto")]
+    crypto: Crypto,
+}
+
+/// Keystore encryption parameters.
+#[derive(Deserialize)]
+struct Crypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    #[serde(with = "serialization::bytes::permissive")]
+    ciphertext: Vec<u8>,
+    kdf: String,
+    kdfparams: Value,
+    #[serde(with = "serialization::bytes::permissive")]
+    mac: Vec<u8>,
+}
+
+/// Keystore cipher parameters.
+#[derive(Deserialize)]
+struct CipherParams {
+    #[serde(with = "serialization::bytes::permissive")]
+    iv: Vec<u8>,
+}
+
+/// Keystore scrypt KDF parameters.
+#[derive(Deserialize)]
+struct ScryptParams {
+    dklen: usize,
+    n: u32,
+    r: u32,
+    p: u32,
+    #[serde(with = "serialization::bytes::permissive")]
+    salt: Vec<u8>,
+}
+
+/// Keystore PBKDF2 KDF parameters.
+#[derive(Deserialize)]
+struct Pbkdf2Params {
+    dklen: usize,
+    c: u32,
+    prf: String,
+    #[serde(with = "serialization::bytes::permissive")]
+    salt: Vec<u8>,
+}
+
+/// Formats random bytes as a version 4 UUID.
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    format!(
+        "{}-{}-{}-{}-{}",
+        hex::encode(&bytes[..4]),
+        hex::encode(&bytes[4..6]),
+        hex::encode(&bytes[6..8]),
+        hex::encode(&bytes[8..10]),
+        hex::encode(&bytes[10..]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ganache::DETERMINISTIC_PRIVATE_KEY;
+    use serde_json::Value;
+
+    #[test]
+    fn encrypt_keystore() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let params = KdfParams {
+            n: 1 << 10,
+            ..Default::default()
+        };
+        let keystore =
+            encrypt_with(&key, "hdwallet", params, [0x11; 32], [0x22; 16], [0x33; 16]).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&keystore).unwrap(),
+            json!({
+                "version": 3,
+                "id": "33333333-3333-4333-b333-333333333333",
+                "address": "90f8bf6a479f320ead074411a4b0e7944ea8c9c1",
+                "crypto": {
+                    "cipher": "aes-128-ctr",
+                    "cipherparams": {
+                        "iv": "22222222222222222222222222222222",
+                    },
+                    "ciphertext": "399da4322c5ee60be0b4de859c5ddac4dd11292a065e87eb3c8a5d643d8732e6",
+                    "kdf": "scrypt",
+                    "kdfparams": {
+                        "dklen": 32,
+                        "n": 1024,
+                        "r": 8,
+                        "p": 1,
+                        "salt": "1111111111111111111111111111111111111111111111111111111111111111",
+                    },
+                    "mac": "6e91087c374b940031538a17c31f8dba14e59e96303e53e9f75febce49554612",
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn decrypt_keystore() {
+        let scrypt = json!({
+            "version": 3,
+            "id": "33333333-3333-4333-b333-333333333333",
+            "address": "90f8bf6a479f320ead074411a4b0e7944ea8c9c1",
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": {
+                    "iv": "22222222222222222222222222222222",
+                },
+                "ciphertext": "399da4322c5ee60be0b4de859c5ddac4dd11292a065e87eb3c8a5d643d8732e6",
+                "kdf": "scrypt",
+                "kdfparams": {
+                    "dklen": 32,
+                    "n": 1024,
+                    "r": 8,
+                    "p": 1,
+                    "salt": "1111111111111111111111111111111111111111111111111111111111111111",
+                },
+                "mac": "6e91087c374b940031538a17c31f8dba14e59e96303e53e9f75febce49554612",
+            },
+        });
+        let pbkdf2 = json!({
+            "version": 3,
+            "id": "33333333-3333-4333-b333-333333333333",
+            "address": "90f8bf6a479f320ead074411a4b0e7944ea8c9c1",
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": {
+                    "iv": "22222222222222222222222222222222",
+                },
+                "ciphertext": "7a2083aa094505230951524785865ee8bf3dc2eb3573831722274fb1a54737d7",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "dklen": 32,
+                    "c": 1024,
+                    "prf": "hmac-sha256",
+                    "salt": "1111111111111111111111111111111111111111111111111111111111111111",
+                },
+                "mac": "15f47a77d9a07f73b9ed591e18b62109b8ae39ac94a11ab1fa710cd8f7749bdf",
+            },
+        });
+
+        for keystore in [scrypt, pbkdf2] {
+            let keystore = keystore.to_string();
+            let key = decrypt(&keystore, "hdwallet").unwrap();
+            assert_eq!(key.secret(), DETERMINISTIC_PRIVATE_KEY);
+
+            let err = decrypt(&keystore, "wrong").unwrap_err();
+            assert!(err.to_string().contains("password is incorrect"));
+        }
+    }
+
+    #[test]
+    fn keystore_roundtrip() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let params = KdfParams {
+            n: 1 << 10,
+            ..Default::default()
+        };
+        let keystore = encrypt(&key, "hdwallet", params).unwrap();
+        assert_eq!(
+            decrypt(&keystore, "hdwallet").unwrap().secret(),
+            DETERMINISTIC_PRIVATE_KEY,
+        );
+    }
+
+    #[test]
+    fn invalid_kdf_params() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        for params in [
+            KdfParams {
+                n: 0,
+                ..Default::default()
+            },
+            KdfParams {
+                n: 1,
+                ..Default::default()
+            },
+            KdfParams {
+                n: 1000,
+                ..Default::default()
+            },
+            KdfParams {
+                n: 1 << 21,
+                ..Default::default()
+            },
+            KdfParams {
+                n: 1 << 10,
+                r: 1 << 20,
+                p: 1,
+            },
+            KdfParams {
+                n: 1 << 10,
+                p: 0,
+                ..Default::default()
+            },
+            KdfParams {
+                n: 1 << 10,
+                p: 17,
+                ..Default::default()
+            },
+        ] {
+            assert!(encrypt(&key, "hdwallet", params).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_excessive_kdf_params() {
+        let keystore = |kdf: &str, kdfparams: Value| {
+            json!({
+                "version": 3,
+                "crypto": {
+                    "cipher": "aes-128-ctr",
+                    "cipherparams": {
+                        "iv": "22222222222222222222222222222222",
+                    },
+                    "ciphertext": "399da4322c5ee60be0b4de859c5ddac4dd11292a065e87eb3c8a5d643d8732e6",
+                    "kdf": kdf,
+                    "kdfparams": kdfparams,
+                    "mac": "6e91087c374b940031538a17c31f8dba14e59e96303e53e9f75febce49554612",
+                },
+            })
+            .to_string()
+        };
+        let salt = "1111111111111111111111111111111111111111111111111111111111111111";
+
+        for keystore in [
+            keystore(
+                "scrypt",
+                json!({ "dklen": 32, "n": 1u32 << 31, "r": 8, "p": 1, "salt": salt }),
+            ),
+            keystore(
+                "scrypt",
+                json!({ "dklen": 32, "n": 1024, "r": 8, "p": 1000000, "salt": salt }),
+            ),
+            keystore(
+                "pbkdf2",
+                json!({ "dklen": 32, "c": u32::MAX, "prf": "hmac-sha256", "salt": salt }),
+            ),
+            keystore(
+                "pbkdf2",
+                json!({ "dklen": 32, "c": 0, "prf": "hmac-sha256", "salt": salt }),
+            ),
+        ] {
+            assert!(decrypt(&keystore, "hdwallet").is_err());
+        }
+    }
+}
@@ -1,11 +1,29 @@
 //! Module implementing public key operations.
 
+use crate::hash;
+use anyhow::{Context as _, Result};
+use ethaddr::Address;
 use k256::elliptic_curve::sec1::ToEncodedPoint as _;
 
 /// A public key.
 pub struct PublicKey(pub k256::PublicKey);
 
 impl PublicKey {
+    /// Parses a public key from its SEC1 encoding, either as 33-byte
+    /// compressed or as 65-byte uncompressed bytes. The 64 bytes of the
+    /// uncompressed point coordinates without the leading `0x04` tag are also
+    /// accepted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let key = if bytes.len() == 64 {
+            k256::PublicKey::from_sec1_bytes(&[&[0x04], bytes].concat())
+        } else {
+            k256::PublicKey::from_sec1_bytes(bytes)
+        }
+        .ok()
+        .with_context(|| format!("invalid {}-byte SEC1 public key", bytes.len()))?;
+        Ok(Self(key))
+    }
+
     /// Returns an uncompressed encoded bytes for the public key.
     pub fn encode_uncompressed(&self) -> [u8; 65] {
         self.0
@@ -14,4 +32,55 @@ impl PublicKey {
             .try_into()
             .expect("unexpected uncompressed private key length")
     }
+
+    /// Returns the SEC1 compressed encoded bytes for the public key.
+    pub fn encode_compressed(&self) -> [u8; 33] {
+        self.0
+            .to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .expect("unexpected compressed public key length")
+    }
+
+    /// Returns the public address for the public key.
+    pub fn address(&self) -> Address {
+        let encoded = self.encode_uncompressed();
+
+        // NOTE: An ethereum address is the last 20 bytes of the keccak hash of
+        // the concatenated elliptic curve coordinates of the public key. Note
+        // that an encoded uncompressed public key is serialized into 65 bytes
+        // where the first byte is a SEC1 tag that is always 0x04 (representing
+        // an uncompressed point) and the subsequent bytes are the coordinates
+        // we want. So discard the first byte for the address calculation.
+        debug_assert_eq!(encoded[0], 0x04);
+        let hash = hash::keccak256(&encoded[1..]);
+
+        Address::from_slice(&hash[12..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account::PrivateKey, ganache::DETERMINISTIC_PRIVATE_KEY};
+
+    #[test]
+    fn from_bytes() {
+        let public = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap().public();
+        let uncompressed = public.encode_uncompressed();
+
+        for bytes in [
+            &uncompressed[..],
+            &uncompressed[1..],
+            &public.encode_compressed()[..],
+        ] {
+            let parsed = PublicKey::from_bytes(bytes).unwrap();
+            assert_eq!(parsed.encode_uncompressed(), uncompressed);
+            assert_eq!(parsed.address(), public.address());
+        }
+
+        assert!(PublicKey::from_bytes(&[]).is_err());
+        assert!(PublicKey::from_bytes(&uncompressed[..64]).is_err());
+        assert!(PublicKey::from_bytes(&[0x04; 65]).is_err());
+    }
 }
@@ -1,9 +1,16 @@
 //! Module containing signature data model.
 
-use anyhow::bail;
+use crate::{account::PublicKey, hash::Hash32};
+use anyhow::{bail, Context as _, Result};
+use ethaddr::Address;
 use ethnum::{AsU256 as _, U256};
-use k256::ecdsa::{self, RecoveryId};
+use k256::ecdsa::{self, RecoveryId, VerifyingKey};
+use serde::{
+    de::{self, Deserializer},
+    Deserialize, Serialize, Serializer,
+};
 use std::{
+    borrow::Cow,
     fmt::{self, Display, Formatter},
     str::FromStr,
 };
@@ -38,16 +45,91 @@ impl Signature {
         }
     }
 
+    /// Returns `true` if the signature's S-value is in the lower half of the
+    /// curve order. Ethereum requires signatures with low S-values, as
+    /// specified in EIP-2, in order to prevent signature malleability.
+    pub fn is_low_s(&self) -> bool {
+        self.0.normalize_s().is_none()
+    }
+
+    /// Normalizes the signature to have a low S-value, flipping the y-parity
+    /// of the signature if the S-value was high. This is a no-op for
+    /// signatures that are already normalized.
+    pub fn normalize_s(&mut self) {
+        *self = self.to_low_s();
+    }
+
+    /// Returns the equivalent signature with a low S-value. Note that negating
+    /// the S-value also flips the y-parity of the signature.
+    pub(crate) fn to_low_s(self) -> Self {
+        match self.0.normalize_s() {
+            Some(signature) => Self(
+                signature,
+                RecoveryId::new(!self.1.is_y_odd(), self.1.is_x_reduced()),
+            ),
+            None => self,
+        }
+    }
+
+    /// Recovers the public key that produced this signature for the specified
+    /// 32-byte message.
+    pub fn recover_public_key(&self, message: impl Into<Hash32>) -> Result<PublicKey> {
+        // NOTE: Signatures with high S-values are valid ECDSA signatures, and
+        // are accepted by the `ecrecover` precompile, but are rejected by the
+        // underlying recovery implementation, so normalize them first.
+        let Signature(signature, recovery_id) = self.to_low_s();
+        let Hash32(message) = message.into();
+        let key = VerifyingKey::recover_from_prehash(&message, &signature, recovery_id)
+            .context("failed to recover public key from signature")?;
+        Ok(PublicKey(key.into()))
+    }
+
+    /// Recovers the address of the account that produced this signature for the
+    /// specified 32-byte message.
+    pub fn recover_address(&self, message: impl Into<Hash32>) -> Result<Address> {
+        Ok(self.recover_public_key(message)?.address())
+    }
+
+    /// Returns the compact 64-byte ERC-2098 representation of the signature,
+    /// where the y-parity is packed into the top bit of the S-value. Note that
+    /// this normalizes the signature to have a low S-value, as the top bit of
+    /// a high S-value would otherwise be set.
+    pub fn to_compact(&self) -> [u8; 64] {
+        let signature = self.to_low_s();
+
+        let mut compact = [0; 64];
+        compact[..32].copy_from_slice(&signature.r().to_be_bytes());
+        compact[32..].copy_from_slice(&signature.s().to_be_bytes());
+        compact[32] |= signature.y_parity().as_u8() << 7;
+        compact
+    }
+
+    /// Parses a signature from its compact 64-byte ERC-2098 representation.
+    pub fn from_compact(bytes: [u8; 64]) -> Result<Self> {
+        let (r, y_parity_and_s) = bytes.split_at(32);
+        let mut s = <[u8; 32]>::try_from(y_parity_and_s).unwrap();
+        let y_parity = s[0] >> 7;
+        s[0] &= 0x7f;
+
+        Self::try_from_parts(r.try_into().unwrap(), s, y_parity)
+    }
+
     /// Creates a signature from its raw parts.
     ///
     /// # Panics
     ///
     /// Panics on invalid signature parts.
     pub fn from_parts(r: [u8; 32], s: [u8; 32], y_parity: u8) -> Self {
-        Self(
-            ecdsa::Signature::from_scalars(r, s).unwrap(),
-            y_parity.try_into().unwrap(),
-        )
+        Self::try_from_parts(r, s, y_parity).expect("invalid signature parts")
+    }
+
+    /// Creates a signature from its raw parts, returning an error on invalid
+    /// signature parts.
+    pub fn try_from_parts(r: [u8; 32], s: [u8; 32], y_parity: u8) -> Result<Self> {
+        Ok(Self(
+            ecdsa::Signature::from_scalars(r, s)?,
+            y_parity.try_into()?,
+        ))
     }
 }
 
@@ -63,31 +145,69 @@ impl Display for Signature {
     }
 }
 
+/// Parses a hex-encoded signature made up of its 32-byte R-value, 32-byte
+/// S-value and a big-endian V-value. The V-value can be any of:
+///
+/// - `0` or `1`: the raw y-parity of the signature
+/// - `27` or `28`: the y-parity offset by 27, as used for `eth_sign`
+/// - `35` or greater: an EIP-155 V-value with chain replay protection, where
+///   the y-parity is `(v - 35) % 2` and the chain ID is ignored
 impl FromStr for Signature {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut signature = [0; 65];
-        hex::decode_to_slice(s, &mut signature)?;
-
-        let v = signature[64];
-        let y_parity = match v {
-            27 => 0,
-            28 => 1,
-            _ => bail!("invalid V-value, must be 27 or 28 but got {v}"),
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))?;
+        if !(65..=96).contains(&bytes.len()) {
+            bail!("invalid signature length of {} bytes", bytes.len());
+        }
+
+        let (rs, v_bytes) = bytes.split_at(64);
+        let mut v = [0; 32];
+        v[32 - v_bytes.len()..].copy_from_slice(v_bytes);
+        let v = U256::from_be_bytes(v);
+
+        let y_parity = match v.as_u8() {
+            _ if v >= 35 => ((v - 35) % 2).as_u8(),
+            0 | 1 => v.as_u8(),
+            27 | 28 => v.as_u8() - 27,
+            _ => bail!("invalid V-value, must be 0, 1, 27, 28 or at least 35 but got {v}"),
         };
 
-        Ok(Self::from_parts(
-            signature[0..32].try_into().unwrap(),
-            signature[32..64].try_into().unwrap(),
+        Self::try_from_parts(
+            rs[0..32].try_into().unwrap(),
+            rs[32..64].try_into().unwrap(),
             y_parity,
-        ))
+        )
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        account::PrivateKey, ganache::DETERMINISTIC_PRIVATE_KEY, hash, message::EthereumMessage,
+    };
+    use hex_literal::hex;
+    use serde_json::json;
 
     #[test]
     fn replay_protection() {
@@ -105,4 +225,202 @@ mod tests {
                1b",
         );
     }
+
+    #[test]
+    fn signature_from_str() {
+        let signature = Signature::from_parts([1; 32], [2; 32], 1);
+        assert_eq!(
+            signature.to_string().parse::<Signature>().unwrap(),
+            signature
+        );
+        assert_eq!(
+            signature.to_string()[2..].parse::<Signature>().unwrap(),
+            signature
+        );
+    }
+
+    #[test]
+    fn signature_from_str_v_values() {
+        let rs = "0x0101010101010101010101010101010101010101010101010101010101010101\
+                    0202020202020202020202020202020202020202020202020202020202020202";
+        for (v, y_parity) in [
+            ("00", 0),
+            ("01", 1),
+            ("1b", 0),
+            ("1c", 1),
+            ("25", 0),
+            ("26", 1),
+            ("0135", 0),
+            ("0136", 1),
+        ] {
+            assert_eq!(
+                format!("{rs}{v}").parse::<Signature>().unwrap(),
+                Signature::from_parts([1; 32], [2; 32], y_parity),
+            );
+        }
+
+        for v in ["", "02", "1a", "1d", "22", &"00".repeat(33)] {
+            assert!(format!("{rs}{v}").parse::<Signature>().is_err());
+        }
+    }
+
+    #[test]
+    fn signature_serialization() {
+        let signature = Signature::from_parts([1; 32], [2; 32], 1);
+        let json = json!(
+            "0x0101010101010101010101010101010101010101010101010101010101010101\
+               0202020202020202020202020202020202020202020202020202020202020202\
+               1c"
+        );
+        assert_eq!(serde_json::to_value(signature).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<Signature>(json).unwrap(),
+            signature
+        );
+
+        for invalid in [
+            json!(
+                "0x0101010101010101010101010101010101010101010101010101010101010101\
+                   0202020202020202020202020202020202020202020202020202020202020202\
+                   02"
+            ),
+            json!(
+                "0x0000000000000000000000000000000000000000000000000000000000000000\
+                   0202020202020202020202020202020202020202020202020202020202020202\
+                   1b"
+            ),
+            json!("0x1234"),
+            json!(42),
+        ] {
+            assert!(serde_json::from_value::<Signature>(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn compact_signatures() {
+        // Test vectors from ERC-2098.
+        let key = PrivateKey::new(hex!(
+            "1234567890123456789012345678901234567890123456789012345678901234"
+        ))
+        .unwrap();
+        for (message, signature, compact) in [
+            (
+                &b"Hello World"[..],
+                Signature::from_parts(
+                    hex!("68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90"),
+                    hex!("7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"),
+                    0,
+                ),
+                hex!(
+                    "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90
+                     7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"
+                ),
+            ),
+            (
+                &b"It's a small(er) world"[..],
+                Signature::from_parts(
+                    hex!("9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76"),
+                    hex!("139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"),
+                    1,
+                ),
+                hex!(
+                    "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76
+                     939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"
+                ),
+            ),
+        ] {
+            let message = EthereumMessage(message).signing_message();
+            assert_eq!(key.sign(message), signature);
+            assert_eq!(signature.to_compact(), compact);
+            assert_eq!(Signature::from_compact(compact).unwrap(), signature);
+            assert_eq!(
+                Signature::from_compact(compact)
+                    .unwrap()
+                    .recover_address(message)
+                    .unwrap(),
+                key.address(),
+            );
+        }
+
+        assert!(Signature::from_compact([0; 64]).is_err());
+    }
+
+    #[test]
+    fn low_s_normalization() {
+        let low = Signature::from_parts(
+            hex!("408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84"),
+            hex!("61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859"),
+            1,
+        );
+        let high = Signature::from_parts(
+            hex!("408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84"),
+            hex!("9e3695536a31c8285cef78499cb0b5c0167532efaa92d8236782bbe9a08e08e8"),
+            0,
+        );
+
+        assert!(low.is_low_s());
+        assert!(!high.is_low_s());
+        assert_eq!(low.to_low_s(), low);
+        assert_eq!(high.to_low_s(), low);
+
+        let message = hash::keccak256(b"\x19Ethereum Signed Message:\n12Hello World!");
+        assert_eq!(
+            high.recover_address(message).unwrap(),
+            low.recover_address(message).unwrap(),
+        );
+    }
+
+    #[test]
+    fn normalize_high_s() {
+        let mut signature = Signature::from_parts(
+            hex!("408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84"),
+            hex!("9e3695536a31c8285cef78499cb0b5c0167532efaa92d8236782bbe9a08e08e8"),
+            0,
+        );
+        assert_eq!(signature.y_parity(), U256::ZERO);
+
+        signature.normalize_s();
+        assert!(signature.is_low_s());
+        assert_eq!(
+            signature.s(),
+            U256::from_be_bytes(hex!(
+                "61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859"
+            )),
+        );
+        assert_eq!(signature.y_parity(), U256::ONE);
+
+        let normalized = signature;
+        signature.normalize_s();
+        assert_eq!(signature, normalized);
+    }
+
+    #[test]
+    fn recover_signer() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let message = hash::keccak256(b"\x19Ethereum Signed Message:\n12Hello World!");
+        let signature = Signature::from_parts(
+            hex!("408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84"),
+            hex!("61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859"),
+            1,
+        );
+
+        assert_eq!(signature.recover_address(message).unwrap(), key.address());
+        assert_eq!(
+            signature
+                .recover_public_key(message)
+                .unwrap()
+                .encode_uncompressed(),
+            key.public().encode_uncompressed(),
+        );
+
+        let wrong_parity = Signature::from_parts(
+            hex!("408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84"),
+            hex!("61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859"),
+            0,
+        );
+        assert_ne!(
+            wrong_parity.recover_address(message).unwrap(),
+            key.address()
+        );
+    }
 }
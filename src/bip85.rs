@@ -0,0 +1,176 @@
+//! BIP-0085 deterministic entropy derivation from a BIP-0032 root key.
+//!
+//! This allows a single master seed to deterministically produce any number of
+//! independent child mnemonics, each of which can be used as its own wallet.
+
+use crate::{
+    hdk::{ExtendedKey, Path},
+    mnemonic::{Language, Mnemonic},
+};
+use anyhow::{ensure, Result};
+use hmac::{Hmac, Mac as _};
+use sha2::Sha512;
+
+/// The BIP-0085 path purpose.
+const PURPOSE: u32 = 83696968;
+
+/// The BIP-0085 application number for BIP-0039 mnemonics.
+const BIP39_APPLICATION: u32 = 39;
+
+/// Derives a BIP-0039 mnemonic from the specified seed with the BIP-0085
+/// derivation path `m/83696968'/39'/{language}'/{words}'/{index}'`.
+///
+/// This method returns an error if the word count is not a valid BIP-0039
+/// mnemonic length (12, 15, 18, 21 or 24).
+pub fn derive_bip39_mnemonic(
+    seed: impl AsRef<[u8]>,
+    language: Language,
+    word_count: usize,
+    index: u32,
+) -> Result<Mnemonic> {
+    bip39_mnemonic(&ExtendedKey::master(seed)?, language, word_count, index)
+}
+
+/// Derives a BIP-0039 mnemonic from the specified root key. See
+/// [`derive_bip39_mnemonic`] for more details.
+pub fn bip39_mnemonic(
+    root: &ExtendedKey,
+    language: Language,
+    word_count: usize,
+    index: u32,
+) -> Result<Mnemonic> {
+    ensure!(
+        matches!(word_count, 12 | 15 | 18 | 21 | 24),
+        "invalid BIP-0085 mnemonic length {word_count}, must be 12, 15, 18, 21 or 24 words",
+    );
+
+    let path = format!(
+        "m/{PURPOSE}'/{BIP39_APPLICATION}'/{}'/{word_count}'/{index}'",
+        language_code(language),
+    )
+    .parse()?;
+    let entropy = derive_entropy(root, &path)?;
+    Mnemonic::from_entropy(language, &entropy[..word_count * 4 / 3])
+}
+
+/// Derives 64 bytes of BIP-0085 entropy from a root key for the specified
+/// derivation path.
+pub fn derive_entropy(root: &ExtendedKey, path: &Path) -> Result<[u8; 64]> {
+    let key = root.derive(path)?.private_key()?;
+
+    let mut hmac = Hmac::<Sha512>::new_from_slice(b"bip-entropy-from-k")?;
+    hmac.update(&key.secret());
+
+    let mut entropy = [0; 64];
+    entropy.copy_from_slice(&hmac.finalize().into_bytes());
+    Ok(entropy)
+}
+
+/// Returns the BIP-0085 language code for a mnemonic language.
+fn language_code(language: Language) -> u32 {
+    match language {
+        Language::English => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    const ROOT: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqt\
+                        QpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+    #[test]
+    fn entropy_vectors() {
+        let root = ROOT.parse::<ExtendedKey>().unwrap();
+        for (path, entropy) in [
+            (
+                "m/83696968'/0'/0'",
+                hex!(
+                    "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f0
+                     0b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"
+                ),
+            ),
+            (
+                "m/83696968'/0'/1'",
+                hex!(
+                    "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872
+                     218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e"
+                ),
+            ),
+        ] {
+            assert_eq!(
+                derive_entropy(&root, &path.parse().unwrap()).unwrap(),
+                entropy,
+            );
+        }
+    }
+
+    #[test]
+    fn bip39_vectors() {
+        let root = ROOT.parse::<ExtendedKey>().unwrap();
+        for (word_count, phrase) in [
+            (
+                12,
+                "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose",
+            ),
+            (
+                15,
+                "aerobic able grant hobby uncle boss filter auction tip exact mixed again \
+                 soda race absorb",
+            ),
+            (
+                18,
+                "near account window bike charge season chef number sketch tomorrow \
+                 excuse sniff circle vital hockey outdoor supply token",
+            ),
+            (
+                21,
+                "feed excite donkey pepper enhance box stock asset submit tomorrow quick \
+                 divert frost setup cream elder unable harbor enlist fabric this",
+            ),
+            (
+                24,
+                "puppy ocean match cereal symbol another shed magic wrap hammer bulb \
+                 intact gadget divorce twin tonight reason outdoor destroy simple \
+                 truth cigar social volcano",
+            ),
+        ] {
+            let mnemonic = bip39_mnemonic(&root, Language::English, word_count, 0).unwrap();
+            assert_eq!(mnemonic.to_phrase(), phrase);
+        }
+    }
+
+    #[test]
+    fn derives_from_seed() {
+        let seed = hex!("000102030405060708090a0b0c0d0e0f");
+        let root = ExtendedKey::master(seed).unwrap();
+        for index in 0..3 {
+            assert_eq!(
+                derive_bip39_mnemonic(seed, Language::English, 12, index)
+                    .unwrap()
+                    .to_phrase(),
+                bip39_mnemonic(&root, Language::English, 12, index)
+                    .unwrap()
+                    .to_phrase(),
+            );
+        }
+        assert_ne!(
+            derive_bip39_mnemonic(seed, Language::English, 12, 0)
+                .unwrap()
+                .to_phrase(),
+            derive_bip39_mnemonic(seed, Language::English, 12, 1)
+                .unwrap()
+                .to_phrase(),
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_word_counts() {
+        let seed = hex!("000102030405060708090a0b0c0d0e0f");
+        for word_count in [0, 11, 13, 16, 25] {
+            assert!(derive_bip39_mnemonic(seed, Language::English, word_count, 0).is_err());
+        }
+    }
+}
@@ -1,55 +1,351 @@
 //! Module containing subcommands.
 
 pub mod address;
+pub mod addresses;
 pub mod export;
 pub mod hash;
 pub mod hex;
+pub mod import_keystore;
+pub mod mnemonic;
 pub mod new;
 pub mod public_key;
+pub mod recover;
 pub mod sign;
+pub mod transaction;
+pub mod verify;
 
-use anyhow::Result;
-use clap::Parser;
-use hdwallet::{account::PrivateKey, hdk, mnemonic::Mnemonic};
+use anyhow::{bail, ensure, Context as _, Result};
+use clap::{
+    builder::{ArgPredicate, Resettable},
+    Arg, ArgGroup, Id, Parser,
+};
+use hdwallet::{
+    account::PrivateKey,
+    hdk,
+    mnemonic::{Mnemonic, Seed},
+};
+use serde_json::Value;
 use std::{
+    borrow::Cow,
+    ffi::{OsStr, OsString},
+    fmt::Display,
     fs,
-    io::{self, Read as _},
-    path::Path,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str,
 };
 
+/// The output format for subcommand results.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// Human readable plain text.
+    Text,
+    /// A JSON object per result, one per line.
+    Json,
+}
+
+impl OutputFormat {
+    /// Prints a result in the output format, either as its plain text
+    /// representation or as a JSON object.
+    fn print(self, text: impl Display, json: Value) {
+        match self {
+            OutputFormat::Text => println!("{text}"),
+            OutputFormat::Json => println!("{json}"),
+        }
+    }
+}
+
 /// Shared account options.
 #[derive(Debug, Parser)]
 struct AccountOptions {
     /// The BIP-0039 mnemonic phrase for seeding the HD wallet.
-    #[clap(short, long, env, hide_env_values = true)]
-    mnemonic: Mnemonic,
+    // NOTE: Individual subcommands can further relax the requirement with
+    // `mut_arg` (see the `address` subcommand).
+    #[clap(
+        short,
+        long,
+        env,
+        hide_env_values = true,
+        required_unless_present = "mnemonic_file"
+    )]
+    mnemonic: Option<Mnemonic>,
+
+    /// Path to a file containing the BIP-0039 mnemonic phrase for seeding the
+    /// HD wallet, so that it does not need to be specified on the command line
+    /// or in the environment. Surrounding whitespace is ignored. This option
+    /// takes precedence over the "--mnemonic" option.
+    #[clap(long)]
+    mnemonic_file: Option<PathBuf>,
 
     /// The password to use with the mnemonic phrase for salting the seed used
     /// for the HD wallet.
     #[clap(long, env, hide_env_values = true, default_value_t)]
     password: String,
 
+    /// Interactively prompt for the mnemonic password without echoing it,
+    /// instead of reading it from the "--password" option or environment.
+    /// This prevents the password from leaking into the shell history. This
+    /// option takes precedence over the "--password" option.
+    #[clap(long)]
+    prompt_password: bool,
+
     /// The BIP-44 account index for deriving a private key from the mnemonic
-    /// seed phrase. The derived key will use the path "m/44'/60'/0'/0/{index}".
+    /// seed phrase. The derived key will use the path "m/44'/60'/0'/0/{index}",
+    /// with the other levels configurable with the "--coin-type", "--account"
+    /// and "--change" options.
     #[clap(long, env, default_value_t = 0)]
     account_index: usize,
 
+    /// The BIP-44 coin type level of the HD path for deriving the account key.
+    #[clap(long, default_value_t = 60, conflicts_with = "hd_path")]
+    coin_type: u32,
+
+    /// The BIP-44 account level of the HD path for deriving the account key.
+    /// Note that this is not the same as the "--account-index" option, which
+    /// specifies the last address index level of the HD path.
+    #[clap(long, default_value_t = 0, conflicts_with = "hd_path")]
+    account: u32,
+
+    /// The BIP-44 change level of the HD path for deriving the account key.
+    #[clap(long, default_value_t = 0, conflicts_with = "hd_path")]
+    change: u32,
+
     /// Manually specified HD path for deriving the account key. This option can
-    /// not be used in conjunction with the "--account-index" option.
+    /// not be used in conjunction with the "--account-index", "--coin-type",
+    /// "--account" or "--change" options.
     #[clap(long, env, conflicts_with = "account_index")]
     hd_path: Option<String>,
 }
 
 impl AccountOptions {
+    /// Returns the seed for the mnemonic and password.
+    pub fn seed(&self) -> Result<Seed> {
+        Ok(self.mnemonic()?.seed(self.password()?))
+    }
+
+    /// Returns the mnemonic, reading it from the mnemonic file if specified.
+    // NOTE: The mnemonic file is not marked as conflicting with the mnemonic,
+    // as values from the environment count as explicitly specified. Instead,
+    // an explicitly specified file takes precedence.
+    fn mnemonic(&self) -> Result<Cow<'_, Mnemonic>> {
+        let Some(path) = &self.mnemonic_file else {
+            let mnemonic = self.mnemonic.as_ref().context("missing mnemonic")?;
+            return Ok(Cow::Borrowed(mnemonic));
+        };
+        let phrase = fs::read_to_string(path)
+            .with_context(|| format!("failed to read mnemonic file '{}'", path.display()))?;
+        Ok(Cow::Owned(phrase.trim().parse()?))
+    }
+
+    /// Returns the mnemonic password, prompting for it if requested.
+    // NOTE: Like with the mnemonic file, prompting is not marked as conflicting
+    // with the password, since it can come from the environment.
+    fn password(&self) -> Result<Cow<'_, str>> {
+        if !self.prompt_password {
+            return Ok(Cow::Borrowed(&self.password));
+        }
+        let password = rpassword::prompt_password("Mnemonic password: ")
+            .context("failed to read mnemonic password")?;
+        Ok(Cow::Owned(password))
+    }
+
     /// Returns the private key for the specified account options.
     pub fn private_key(&self) -> Result<PrivateKey> {
-        let seed = self.mnemonic.seed(&self.password);
-        let path = match &self.hd_path {
-            None => hdk::Path::for_index(self.account_index),
+        hdk::derive(self.seed()?, &self.hd_path()?)
+    }
+
+    /// Returns the private keys for the specified number of consecutive
+    /// accounts, starting at the configured account index.
+    pub fn private_keys(&self, count: usize) -> Result<Vec<PrivateKey>> {
+        if count == 1 {
+            return Ok(vec![self.private_key()?]);
+        }
+        self.private_keys_from(self.account_index, count)
+    }
+
+    /// Returns the private keys for the specified number of consecutive
+    /// accounts, starting at the specified account index.
+    pub fn private_keys_from(&self, start: usize, count: usize) -> Result<Vec<PrivateKey>> {
+        ensure!(
+            self.hd_path.is_none(),
+            "multiple accounts can not be derived from a manually specified HD path",
+        );
+
+        let derivation = self.derivation()?;
+        (0..count)
+            .map(|i| {
+                let index = start.checked_add(i).context("account index overflow")?;
+                derivation.child(u32::try_from(index).context("account index out of range")?)
+            })
+            .collect()
+    }
+
+    /// Returns the HD path used for deriving the account key.
+    pub fn hd_path(&self) -> Result<hdk::Path> {
+        Ok(match &self.hd_path {
+            None => self.path_for_index(self.account_index)?,
             Some(hd_path) => hd_path.parse()?,
+        })
+    }
+
+    /// Returns the BIP-44 HD path for the specified account index.
+    pub fn path_for_index(&self, index: usize) -> Result<hdk::Path> {
+        hdk::Path::bip44(self.coin_type, self.account, self.change, index)
+    }
+
+    /// Returns a cached derivation for consecutive account indices.
+    pub fn derivation(&self) -> Result<hdk::Derivation> {
+        hdk::Derivation::bip44(self.seed()?, self.coin_type, self.account, self.change)
+    }
+}
+
+/// The keys that are supported in account configuration files.
+const CONFIG_KEYS: &[&str] = &["MNEMONIC", "PASSWORD", "ACCOUNT_INDEX", "HD_PATH"];
+
+/// Help for the "--config" option, which is handled by [`parse_options`]
+/// instead of the command line parser.
+pub const CONFIG_HELP: &str = "\
+Account options can be loaded from a dotenv-style configuration file with \
+\"--config <PATH>\", containing \"KEY=value\" lines for the \"MNEMONIC\", \
+\"PASSWORD\", \"ACCOUNT_INDEX\" and \"HD_PATH\" options. Values from the file \
+are used as defaults, and can be overridden by command line options or \
+environment variables.";
+
+/// Parses the command line options, using values from the account
+/// configuration file specified with the "--config" option as defaults for
+/// the account options.
+///
+/// The configuration needs to be loaded before parsing the command line
+/// arguments, as it affects how they get parsed (for example, whether or not
+/// the mnemonic is missing). Because of this, the "--config" option is not
+/// known to the command line parser and is documented with [`CONFIG_HELP`]
+/// instead.
+pub fn parse_options<T: Parser>(args: impl IntoIterator<Item = OsString>) -> Result<T> {
+    let (args, config) = load_config(args)?;
+    let command = with_config_defaults(T::command(), &parse_config(&config)?);
+    Ok(T::from_arg_matches(&command.get_matches_from(args))?)
+}
+
+/// Reads the account configuration file specified with the "--config" option,
+/// returning the remaining command line arguments along with the contents of
+/// the configuration file (empty if none was specified).
+fn load_config(args: impl IntoIterator<Item = OsString>) -> Result<(Vec<OsString>, String)> {
+    let mut args = args.into_iter();
+    let mut remaining = Vec::new();
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            remaining.push(arg);
+            remaining.extend(args.by_ref());
+            break;
+        }
+        if arg == "--config" {
+            let value = args.next().context("missing '--config' value")?;
+            ensure!(path.is_none(), "'--config' can only be specified once");
+            path = Some(PathBuf::from(value));
+        } else if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+            ensure!(path.is_none(), "'--config' can only be specified once");
+            path = Some(PathBuf::from(value));
+        } else {
+            remaining.push(arg);
+        }
+    }
+    let Some(path) = path else {
+        return Ok((remaining, String::new()));
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        let mode = fs::metadata(&path)
+            .with_context(|| format!("failed to read config file '{}'", path.display()))?
+            .permissions()
+            .mode();
+        if mode & 0o004 != 0 {
+            eprintln!(
+                "WARNING: Config file '{}' containing wallet secrets is world-readable. \
+                 Consider restricting its permissions with `chmod 600`.",
+                path.display(),
+            );
+        }
+    }
+
+    let config = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read config file '{}'", path.display()))?;
+    Ok((remaining, config))
+}
+
+/// Uses configuration values as defaults for the options of the command and
+/// all of its subcommands that read the corresponding environment variables.
+///
+/// Unlike values from the environment, defaults do not count as explicitly
+/// specified, so they don't conflict with command line options. Configured
+/// values are also not used when a conflicting option is specified, so that,
+/// for example, a configured HD path does not take precedence over an
+/// "--account-index" specified on the command line.
+fn with_config_defaults(command: clap::Command, config: &[(&str, &str)]) -> clap::Command {
+    let mut command =
+        command.mut_subcommands(|subcommand| with_config_defaults(subcommand, config));
+    for &(key, value) in config {
+        let Some(arg) = command
+            .get_arguments()
+            .find(|arg| arg.get_env() == Some(OsStr::new(key)))
+        else {
+            continue;
+        };
+
+        let conflicts = |a: &Arg, b: &Arg| {
+            command
+                .get_arg_conflicts_with(a)
+                .iter()
+                .any(|conflict| conflict.get_id() == b.get_id())
         };
-        hdk::derive(seed, &path)
+        let overrides = command
+            .get_arguments()
+            .filter(|other| conflicts(arg, other) || conflicts(other, arg))
+            .map(|other| other.get_id().clone())
+            .collect::<Vec<_>>();
+        let id = arg.get_id().clone();
+        let default = Resettable::from(arg.get_default_values().first().cloned());
+
+        // NOTE: Clap only adds explicitly specified options to groups, so use
+        // one for detecting conflicting options, in which case the original
+        // default value is used instead of the configured one.
+        let group = format!("{id}_overrides");
+        command = command
+            .group(ArgGroup::new(group.clone()).args(overrides).multiple(true))
+            .mut_arg(id, |arg| {
+                arg.default_value(value.to_owned())
+                    .default_value_if(group, ArgPredicate::IsPresent, default)
+                    .hide_default_value(true)
+                    .required_unless_present(Resettable::<Id>::Reset)
+            });
     }
+    command
+}
+
+/// Parses a dotenv-style configuration file.
+fn parse_config(config: &str) -> Result<Vec<(&str, &str)>> {
+    config
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let (key, value) = line
+                .split_once('=')
+                .with_context(|| format!("invalid config line {number}, expected KEY=value"))?;
+            let (key, value) = (key.trim(), value.trim());
+            if !CONFIG_KEYS.contains(&key) {
+                bail!("unsupported config key '{key}' on line {number}");
+            }
+            let value = ['"', '\'']
+                .into_iter()
+                .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+                .unwrap_or(value);
+            Ok((key, value))
+        })
+        .collect()
 }
 
 /// Permissive hex encoding parsing, ignoring all whitespace and accepting bot
@@ -63,6 +359,22 @@ fn permissive_hex(s: &str) -> Result<Box<[u8]>> {
     Ok(bytes.into_boxed_slice())
 }
 
+/// Decodes message contents as a permissive hex string when requested,
+/// otherwise returning the raw message bytes unchanged.
+fn decode_message(message: Vec<u8>, hex: bool) -> Result<Vec<u8>> {
+    if !hex {
+        return Ok(message);
+    }
+    Ok(permissive_hex(str::from_utf8(&message)?)?.into_vec())
+}
+
+/// Permissive hex encoding parsing of a 32-byte digest.
+fn permissive_hex_digest(s: &str) -> Result<[u8; 32]> {
+    permissive_hex(s)?[..]
+        .try_into()
+        .context("message for signing must be exactly 32 bytes long")
+}
+
 /// Read input for the specified path with `-` used to signify standard in.
 fn read_input(input: &Path) -> Result<Vec<u8>> {
     let data = match input.to_str() {
@@ -76,3 +388,215 @@ fn read_input(input: &Path) -> Result<Vec<u8>> {
 
     Ok(data)
 }
+
+/// Reads JSON input for the specified path like [`read_input`]. In lenient
+/// mode, the input may contain `//` and `/* */` comments as well as trailing
+/// commas, which are removed so that it can be parsed as strict JSON.
+fn read_json_input(input: &Path, lenient: bool) -> Result<Vec<u8>> {
+    let json = read_input(input)?;
+    if !lenient {
+        return Ok(json);
+    }
+    lenient_json(json)
+}
+
+/// Converts lenient JSON with comments and trailing commas to strict JSON.
+/// Comments and trailing commas are replaced with whitespace, so that line and
+/// column numbers in JSON parsing errors match the original input.
+fn lenient_json(mut json: Vec<u8>) -> Result<Vec<u8>> {
+    let mut trailing_comma = None;
+    let mut previous = None;
+    let mut i = 0;
+    while i < json.len() {
+        match (json[i], json.get(i + 1)) {
+            (b'"', _) => {
+                i += 1;
+                while *json.get(i).context("unterminated JSON string")? != b'"' {
+                    i += if json[i] == b'\\' { 2 } else { 1 };
+                }
+                trailing_comma = None;
+            }
+            (b'/', Some(b'/')) => {
+                while i < json.len() && json[i] != b'\n' {
+                    json[i] = b' ';
+                    i += 1;
+                }
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                let end = json[i + 2..]
+                    .windows(2)
+                    .position(|window| window == b"*/")
+                    .context("unterminated JSON comment")?
+                    + i
+                    + 4;
+                for byte in &mut json[i..end] {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+                i = end;
+                continue;
+            }
+            (b',', _) => {
+                // NOTE: Only commas following a value can be trailing commas,
+                // so that inputs like `{,}` are still rejected.
+                trailing_comma = match previous {
+                    None | Some(b'{' | b'[' | b',') => None,
+                    Some(_) => Some(i),
+                };
+            }
+            (b'}' | b']', _) => {
+                if let Some(comma) = trailing_comma.take() {
+                    json[comma] = b' ';
+                }
+            }
+            (byte, _) if byte.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            _ => trailing_comma = None,
+        }
+        previous = Some(json[i]);
+        i += 1;
+    }
+
+    Ok(json)
+}
+
+/// Opens the input for the specified path with `-` used to signify standard
+/// in, for reading it incrementally.
+fn open_input(input: &Path) -> Result<Box<dyn Read>> {
+    Ok(match input.to_str() {
+        Some("-") => Box::new(io::stdin().lock()),
+        _ => Box::new(fs::File::open(input)?),
+    })
+}
+
+/// Processes each of the specified inputs, printing one result per line in the
+/// specified output format. When more than one input is specified, each result
+/// is labeled with its path.
+fn for_each_input(
+    inputs: &[PathBuf],
+    output: OutputFormat,
+    mut process: impl FnMut(Vec<u8>) -> Result<(String, Value)>,
+) -> Result<()> {
+    for_each_reader(inputs, output, |mut reader| {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        process(data)
+    })
+}
+
+/// Processes each of the specified inputs as readers, so that inputs do not
+/// need to be buffered in memory. Results are printed like with
+/// [`for_each_input`].
+fn for_each_reader(
+    inputs: &[PathBuf],
+    output: OutputFormat,
+    mut process: impl FnMut(Box<dyn Read>) -> Result<(String, Value)>,
+) -> Result<()> {
+    let stdin = inputs.iter().filter(|input| input.to_str() == Some("-"));
+    ensure!(
+        stdin.count() <= 1,
+        "standard in can only be specified once as an input",
+    );
+
+    let label = inputs.len() > 1;
+    for input in inputs {
+        let (text, mut json) = open_input(input)
+            .and_then(&mut process)
+            .with_context(|| format!("error processing '{}'", input.display()))?;
+        match output {
+            OutputFormat::Text if label => println!("{}: {text}", input.display()),
+            OutputFormat::Text => println!("{text}"),
+            OutputFormat::Json => {
+                if label {
+                    json["path"] = input.display().to_string().into();
+                }
+                println!("{json}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn prompt_password_takes_precedence_over_password() {
+        let options = AccountOptions::try_parse_from([
+            "hdwallet",
+            "--mnemonic-file",
+            "mnemonic.txt",
+            "--password",
+            "secret",
+            "--prompt-password",
+        ])
+        .unwrap();
+        assert!(options.prompt_password);
+    }
+
+    fn parse_lenient(json: &str) -> Result<Value> {
+        let strict = lenient_json(json.as_bytes().to_vec())?;
+        Ok(serde_json::from_slice(&strict)?)
+    }
+
+    #[test]
+    fn lenient_json_comments_and_trailing_commas() {
+        for (json, value) in [
+            (
+                r#"{
+                    // line comment
+                    "a": 1, /* block
+                    comment */ "b": [1, 2,],
+                }"#,
+                json!({ "a": 1, "b": [1, 2] }),
+            ),
+            (
+                r#"{ "url": "https://example.com", "glob": "/* not a comment */" }"#,
+                json!({ "url": "https://example.com", "glob": "/* not a comment */" }),
+            ),
+            (
+                r#"{ "quote": "\"// still a string\"", "slash": "\\", }"#,
+                json!({ "quote": "\"// still a string\"", "slash": "\\" }),
+            ),
+            ("[1, /* comment */ ]", json!([1])),
+            ("[1, // comment\n]", json!([1])),
+            ("{}", json!({})),
+            ("[]", json!([])),
+        ] {
+            assert_eq!(parse_lenient(json).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn lenient_json_preserves_positions() {
+        let json = "{\n  // comment\n  \"a\": 1,\n}";
+        let strict = lenient_json(json.as_bytes().to_vec()).unwrap();
+        assert_eq!(strict.len(), json.len());
+        assert_eq!(
+            strict.iter().filter(|&&byte| byte == b'\n').count(),
+            json.matches('\n').count(),
+        );
+    }
+
+    #[test]
+    fn lenient_json_errors() {
+        for json in [
+            "{,}",
+            "[,]",
+            "[1,,]",
+            r#"{ "a": 1 /* unterminated"#,
+            "[1 /*/ ]",
+            r#"{ "a": "unterminated }"#,
+            r#"{ "a": "\"#,
+        ] {
+            assert!(parse_lenient(json).is_err(), "{json}");
+        }
+    }
+}
@@ -1,17 +1,220 @@
 //! Module implementing the `address` subcommand for displaying the public
 //! address for corresponding account.
 
-use crate::cmd::AccountOptions;
+use crate::cmd::{self, AccountOptions, OutputFormat};
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use ethaddr::Address;
+use ethnum::U256;
+use hdwallet::{
+    account::{self, PrivateKey, PublicKey},
+    hash::{self, Hash32},
+};
+use serde_json::json;
 
 #[derive(Debug, Parser)]
+// NOTE: The mnemonic is not required when the address is computed directly
+// from key material. We can't mark the key options as conflicting with the
+// mnemonic, as values from the environment count as explicitly specified.
+#[clap(
+    mut_arg("mnemonic", |arg| {
+        arg.required_unless_present_any(["public_key", "private_key"])
+    }),
+    subcommand_negates_reqs = true
+)]
 pub struct Options {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     #[clap(flatten)]
     account: AccountOptions,
+
+    /// Print the addresses for this number of consecutive account indices
+    /// instead of a single address, one per line.
+    #[clap(short = 'n', long, conflicts_with = "hd_path")]
+    count: Option<usize>,
+
+    /// The first account index to print addresses for when specifying
+    /// "--count". Defaults to the "--account-index" value.
+    #[clap(long, requires = "count", conflicts_with = "account_index")]
+    start: Option<usize>,
+
+    /// Prefix each address printed with "--count" with the HD path it was
+    /// derived from, for cross-referencing with hardware wallet displays.
+    #[clap(long, requires = "count")]
+    show_path: bool,
+
+    /// Print the address for a hex-encoded SEC1 public key instead of deriving
+    /// it from the mnemonic. Both compressed and uncompressed keys are
+    /// supported.
+    #[clap(
+        long,
+        conflicts_with_all = ["private_key", "count", "start"],
+        conflicts_with_all = DERIVATION_OPTIONS,
+    )]
+    public_key: Option<String>,
+
+    /// Print the address for a hex-encoded 32-byte private key instead of
+    /// deriving it from the mnemonic.
+    #[clap(
+        long,
+        conflicts_with_all = ["count", "start"],
+        conflicts_with_all = DERIVATION_OPTIONS,
+    )]
+    private_key: Option<String>,
+
+    /// Print addresses with the chain-specific EIP-1191 checksum for the
+    /// specified chain ID, as used by networks like RSK. Addresses use the
+    /// standard EIP-55 checksum when omitted.
+    #[clap(long)]
+    chain_id: Option<u64>,
+
+    /// Print addresses as lowercase hex without any checksum, for tools that
+    /// don't accept mixed-case addresses.
+    #[clap(long, conflicts_with = "chain_id")]
+    raw: bool,
+
+    /// Print addresses as lowercase hex without the "0x" prefix. This implies
+    /// the "--raw" option.
+    #[clap(long, conflicts_with = "chain_id")]
+    no_prefix: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compute the address of a contract deployed by an account, instead of
+    /// the address of the account itself.
+    Contract {
+        /// The address of the account, or factory contract for "--create2",
+        /// deploying the contract.
+        #[clap(long)]
+        sender: Address,
+
+        /// The nonce of the contract creation transaction.
+        #[clap(long, required_unless_present = "create2")]
+        nonce: Option<u64>,
+
+        /// Compute the address of a contract deployed with the CREATE2 opcode
+        /// from a salt and init code hash, instead of with a transaction.
+        #[clap(long, conflicts_with = "nonce", requires_all = ["salt", "init_code_hash"])]
+        create2: bool,
+
+        /// The 32-byte hex-encoded salt used for CREATE2 deployment.
+        #[clap(long, requires = "create2")]
+        salt: Option<Hash32>,
+
+        /// The 32-byte hex-encoded Keccak-256 hash of the contract init code
+        /// used for CREATE2 deployment.
+        #[clap(long, requires = "create2")]
+        init_code_hash: Option<Hash32>,
+    },
+}
+
+/// Account options for deriving keys from the mnemonic, which have no effect
+/// when computing the address from key material directly.
+// NOTE: Options that can be read from the environment are not included, as
+// values from the environment count as explicitly specified and would cause
+// spurious conflicts. They are silently ignored instead, like the mnemonic.
+const DERIVATION_OPTIONS: [&str; 5] = [
+    "mnemonic_file",
+    "prompt_password",
+    "coin_type",
+    "account",
+    "change",
+];
+
+impl Options {
+    /// Formats an address as specified by the options.
+    fn format(&self, address: Address) -> String {
+        if self.no_prefix {
+            hex::encode(*address)
+        } else if self.raw {
+            format!("0x{}", hex::encode(*address))
+        } else {
+            checksum(address, self.chain_id)
+        }
+    }
 }
 
-pub fn run(options: Options) -> Result<()> {
-    println!("{}", options.account.private_key()?.address());
+pub fn run(options: Options, output: OutputFormat) -> Result<()> {
+    let print = |address| {
+        let address = options.format(address);
+        output.print(&address, json!({ "address": address }));
+    };
+
+    if let Some(Command::Contract {
+        sender,
+        nonce,
+        salt,
+        init_code_hash,
+        ..
+    }) = &options.command
+    {
+        let address = match (nonce, salt, init_code_hash) {
+            (Some(nonce), _, _) => account::contract_address(*sender, U256::from(*nonce)),
+            (_, Some(salt), Some(init_code_hash)) => {
+                account::create2_address(*sender, *salt, *init_code_hash)
+            }
+            _ => unreachable!("argument requirements enforced by clap"),
+        };
+        print(address);
+        return Ok(());
+    }
+    if let Some(public_key) = &options.public_key {
+        let public_key = PublicKey::from_bytes(&cmd::permissive_hex(public_key)?)?;
+        print(public_key.address());
+        return Ok(());
+    }
+    if let Some(private_key) = &options.private_key {
+        let private_key = private_key.parse::<PrivateKey>()?;
+        print(private_key.address());
+        return Ok(());
+    }
+
+    let Some(count) = options.count else {
+        print(options.account.private_key()?.address());
+        return Ok(());
+    };
+
+    let account = &options.account;
+    let start = options.start.unwrap_or(account.account_index);
+    for (index, key) in (start..).zip(account.private_keys_from(start, count)?) {
+        let address = key.address();
+        if options.show_path {
+            let path = account.path_for_index(index)?;
+            let address = options.format(address);
+            output.print(
+                format_args!("{path}  {address}"),
+                json!({ "path": path.to_string(), "address": address }),
+            );
+        } else {
+            print(address);
+        }
+    }
     Ok(())
 }
+
+/// Formats an address with its checksum. Without a chain ID, this is the
+/// standard EIP-55 checksum. With a chain ID, the EIP-1191 checksum is used
+/// instead, which mixes the chain ID into the hashed data.
+fn checksum(address: Address, chain_id: Option<u64>) -> String {
+    let Some(chain_id) = chain_id else {
+        return address.to_string();
+    };
+
+    let hex = hex::encode(*address);
+    let digest = hash::keccak256(format!("{chain_id}0x{hex}"));
+    let checksummed = hex
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (digest[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+    format!("0x{checksummed}")
+}
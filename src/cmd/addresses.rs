@@ -0,0 +1,46 @@
+//! Module implementing the `addresses` subcommand for deriving watch-only
+//! receive addresses from an extended public key.
+
+use anyhow::{ensure, Context as _, Result};
+use clap::Parser;
+use hdwallet::hdk::{Component, ExtendedKey};
+
+#[derive(Debug, Parser)]
+pub struct Options {
+    /// The Base58 encoded account extended public key, for example the `xpub`
+    /// for the `m/44'/60'/0'` HD path. Receive addresses are derived with
+    /// non-hardened public derivation at `{xpub}/0/{index}`, so no private
+    /// key material is required.
+    #[clap(long, env)]
+    xpub: ExtendedKey,
+
+    /// The number of consecutive receive addresses to print, one per line.
+    #[clap(short = 'n', long, default_value_t = 1)]
+    count: u32,
+
+    /// The index of the first receive address to print.
+    #[clap(long, default_value_t = 0)]
+    start: u32,
+}
+
+pub fn run(options: Options) -> Result<()> {
+    ensure!(
+        !options.xpub.is_private(),
+        "expected an extended public key but got an extended private key"
+    );
+
+    // NOTE: Indices at or above 2^31 are reserved for hardened derivation,
+    // which is not possible without the private key.
+    let end = options
+        .start
+        .checked_add(options.count)
+        .filter(|end| *end <= 1 << 31)
+        .context("receive address index out of range")?;
+
+    let receive = options.xpub.derive_public(Component::Normal(0))?;
+    for index in options.start..end {
+        let child = receive.derive_public(Component::Normal(index))?;
+        println!("{}", child.public_key().address());
+    }
+    Ok(())
+}
@@ -1,18 +1,64 @@
 //! Module implementing the `export` subcommand for displaying an account's
 //! private key.
 
-use crate::cmd::AccountOptions;
+use crate::cmd::{AccountOptions, OutputFormat};
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use ethnum::U256;
+use serde_json::json;
 
 #[derive(Debug, Parser)]
 pub struct Options {
     #[clap(flatten)]
     account: AccountOptions,
+
+    /// The number of consecutive accounts to export, starting at the account
+    /// index.
+    #[clap(short = 'n', long, default_value_t = 1, conflicts_with = "hd_path")]
+    count: usize,
+
+    /// The output format for the exported private keys. The Hardhat format is
+    /// always printed as JSON.
+    #[clap(long, value_enum, default_value_t = Format::Hex)]
+    format: Format,
+
+    /// The initial balance in Wei for each account when exporting in the
+    /// Hardhat format. Defaults to 10000 Ether, like Hardhat itself.
+    #[clap(long, default_value = "10000000000000000000000")]
+    balance: U256,
+}
+
+/// The output format for exported private keys.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    /// Hex-encoded private keys, one per line.
+    Hex,
+    /// JSON array of `{ "privateKey", "balance" }` objects, as expected by
+    /// the `networks.hardhat.accounts` option in a Hardhat configuration.
+    Hardhat,
 }
 
-pub fn run(options: Options) -> Result<()> {
-    let key = options.account.private_key()?;
-    println!("0x{}", hex::encode(key.secret()));
+pub fn run(options: Options, output: OutputFormat) -> Result<()> {
+    let keys = options.account.private_keys(options.count)?;
+    match options.format {
+        Format::Hex => {
+            for key in keys {
+                let private_key = format!("0x{}", hex::encode(key.secret()));
+                output.print(&private_key, json!({ "privateKey": private_key }));
+            }
+        }
+        Format::Hardhat => {
+            let accounts = keys
+                .iter()
+                .map(|key| {
+                    json!({
+                        "privateKey": format!("0x{}", hex::encode(key.secret())),
+                        "balance": options.balance.to_string(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&accounts)?);
+        }
+    }
     Ok(())
 }
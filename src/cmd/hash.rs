@@ -1,18 +1,28 @@
 //! Module implementing the `hash` subcommand for `keccak256` hashing data.
 
-use crate::cmd;
+use crate::cmd::{self, OutputFormat};
 use anyhow::Result;
 use clap::Parser;
+use ethaddr::Address;
 use hdwallet::{
-    account::Signature, hash, message::EthereumMessage, transaction::Transaction,
+    account::Signature,
+    hash::{Hash32, Keccak256Hasher},
+    message::{EthereumMessage, ValidatorMessage},
+    transaction::Transaction,
     typeddata::TypedData,
 };
-use std::path::PathBuf;
+use serde_json::{json, Map, Value};
+use std::{io, path::PathBuf};
 
 #[derive(Debug, Parser)]
 pub struct Options {
     #[clap(subcommand)]
     input: Input,
+
+    /// Accept JSON input files with "//" and "/* */" comments and trailing
+    /// commas, for hand-edited transactions and typed data.
+    #[clap(long, global = true)]
+    json5: bool,
 }
 
 #[derive(Debug, Parser)]
@@ -30,11 +40,25 @@ enum Input {
 
     /// Hash an Ethereum message.
     Message {
-        /// Path to the message to hash in the "eth_sign" scheme. This message
-        /// will be prefixed with "\x19Ethereum Signed Message:\n" and its
-        /// length before hashing.
-        #[clap(name = "MESSAGE")]
-        message: PathBuf,
+        /// Paths to the messages to hash in the "eth_sign" scheme. These
+        /// messages will be prefixed with "\x19Ethereum Signed Message:\n" and
+        /// their length before hashing. When multiple messages are specified,
+        /// each hash is labeled with its message path.
+        #[clap(name = "MESSAGE", required = true)]
+        messages: Vec<PathBuf>,
+    },
+
+    /// Hash data with an intended validator (EIP-191 version 0x00).
+    Validator {
+        /// Path to the data to hash. The data is prefixed with "\x19\x00" and
+        /// the validator address before hashing.
+        #[clap(name = "DATA")]
+        data: PathBuf,
+
+        /// The address of the intended validator, usually the contract that
+        /// verifies the signature.
+        #[clap(long)]
+        validator: Address,
     },
 
     /// Hash EIP-712 typed data.
@@ -48,50 +72,114 @@ enum Input {
         /// the domain separator.
         #[clap(short, long)]
         message_hash: bool,
+
+        /// Only compute the domain separator of the typed data.
+        #[clap(short, long, conflicts_with = "message_hash")]
+        domain_separator: bool,
+
+        /// Print the type hash of each type defined in the typed data instead,
+        /// one `TypeName: 0x<typeHash>` line per type. This is useful for
+        /// cross-checking type hashes that are hardcoded in contracts.
+        #[clap(long, conflicts_with_all = ["message_hash", "domain_separator"])]
+        type_hashes: bool,
+
+        /// Compute the struct hash of the first value of the named type in
+        /// the message instead of the primary type, without mixing in the
+        /// domain separator.
+        #[clap(
+            long = "type",
+            value_name = "NAME",
+            conflicts_with_all = ["message_hash", "domain_separator", "type_hashes"],
+        )]
+        kind: Option<String>,
     },
 
     /// Hash raw data.
     Data {
-        /// Path to the data to hash. Use `-` for standard in.
-        #[clap(name = "DATA")]
-        data: PathBuf,
+        /// Paths to the data to hash. Use `-` for standard in. When multiple
+        /// paths are specified, each hash is labeled with its data path.
+        #[clap(name = "DATA", required = true)]
+        data: Vec<PathBuf>,
     },
 }
 
-pub fn run(options: Options) -> Result<()> {
-    let hash = match options.input {
+pub fn run(options: Options, output: OutputFormat) -> Result<()> {
+    let print = |hash: Hash32| output.print(hash, to_json(hash));
+    match options.input {
         Input::Transaction {
             transaction,
             signature,
         } => {
-            let transaction =
-                serde_json::from_slice::<Transaction>(&cmd::read_input(&transaction)?)?;
-            match signature {
-                Some(signature) => hash::keccak256(transaction.encode(signature)),
+            let input = cmd::read_json_input(&transaction, options.json5)?;
+            let transaction = serde_json::from_slice::<Transaction>(&input)?;
+            let hash = match signature {
+                Some(signature) => transaction.hash(signature),
                 None => transaction.signing_message(),
-            }
+            };
+            print(hash);
         }
-        Input::Message { message } => {
-            let message = EthereumMessage(cmd::read_input(&message)?);
-            message.signing_message()
+        Input::Message { messages } => {
+            cmd::for_each_input(&messages, output, |message| {
+                let hash = EthereumMessage(message).signing_message();
+                Ok((hash.to_string(), to_json(hash)))
+            })?;
+        }
+        Input::Validator { data, validator } => {
+            let message = ValidatorMessage {
+                validator,
+                data: cmd::read_input(&data)?,
+            };
+            print(message.signing_message());
         }
         Input::TypedData {
             typed_data,
             message_hash,
+            domain_separator,
+            type_hashes,
+            kind,
         } => {
-            let typed_data = serde_json::from_slice::<TypedData>(&cmd::read_input(&typed_data)?)?;
-            if message_hash {
-                typed_data.message_hash()
+            let input = cmd::read_json_input(&typed_data, options.json5)?;
+            let typed_data = serde_json::from_slice::<TypedData>(&input)?;
+            if type_hashes {
+                let type_hashes = typed_data.type_hashes()?;
+                match output {
+                    OutputFormat::Text => {
+                        for (kind, hash) in type_hashes {
+                            println!("{kind}: {hash}");
+                        }
+                    }
+                    OutputFormat::Json => {
+                        let type_hashes = type_hashes
+                            .into_iter()
+                            .map(|(kind, hash)| (kind.to_owned(), Value::from(hash.to_string())))
+                            .collect::<Map<_, _>>();
+                        println!("{}", json!({ "typeHashes": type_hashes }));
+                    }
+                }
+            } else if let Some(kind) = kind {
+                print(typed_data.struct_hash_of(&kind)?);
+            } else if message_hash {
+                print(typed_data.message_hash());
+            } else if domain_separator {
+                print(typed_data.domain_separator());
             } else {
-                typed_data.signing_message()
+                print(typed_data.signing_message());
             }
         }
         Input::Data { data } => {
-            let data = cmd::read_input(&data)?;
-            hash::keccak256(data)
+            cmd::for_each_reader(&data, output, |mut data| {
+                let mut hasher = Keccak256Hasher::new();
+                io::copy(&mut data, &mut hasher)?;
+                let hash = Hash32(hasher.finalize());
+                Ok((hash.to_string(), to_json(hash)))
+            })?;
         }
-    };
-    println!("0x{}", hex::encode(hash));
+    }
 
     Ok(())
 }
+
+/// Returns the JSON object representation of a hash result.
+fn to_json(hash: Hash32) -> Value {
+    json!({ "hash": hash.to_string() })
+}
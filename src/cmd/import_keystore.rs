@@ -0,0 +1,26 @@
+//! Module implementing the `import-keystore` subcommand for decrypting an
+//! account from a JSON keystore.
+
+use crate::cmd;
+use anyhow::Result;
+use clap::Parser;
+use hdwallet::account::keystore;
+use std::{path::PathBuf, str};
+
+#[derive(Debug, Parser)]
+pub struct Options {
+    /// Path to the JSON keystore to import. Use `-` for standard in.
+    #[clap(name = "FILE")]
+    keystore: PathBuf,
+
+    /// The password for decrypting the keystore.
+    #[clap(long, env = "KEYSTORE_PASSWORD", hide_env_values = true)]
+    password: String,
+}
+
+pub fn run(options: Options) -> Result<()> {
+    let keystore = cmd::read_input(&options.keystore)?;
+    let key = keystore::decrypt(str::from_utf8(&keystore)?, &options.password)?;
+    println!("{}", key.address());
+    Ok(())
+}
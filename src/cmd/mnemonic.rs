@@ -0,0 +1,49 @@
+//! Module implementing the `mnemonic` subcommand with utilities for working
+//! with BIP-0039 mnemonic phrases.
+
+use anyhow::Result;
+use clap::Parser;
+use hdwallet::mnemonic::{Language, Mnemonic};
+
+#[derive(Debug, Parser)]
+pub struct Options {
+    #[clap(subcommand)]
+    op: Op,
+}
+
+#[derive(Debug, Parser)]
+enum Op {
+    /// Compute all valid final words for an incomplete mnemonic phrase.
+    LastWords {
+        /// The mnemonic phrase without its final word. For example, the first
+        /// 11 words of a 12-word mnemonic or the first 23 words of a 24-word
+        /// mnemonic.
+        #[clap(name = "PHRASE")]
+        phrase: String,
+    },
+
+    /// Expand a mnemonic phrase of abbreviated words, such as the first four
+    /// letters of each word, into the full mnemonic phrase.
+    Expand {
+        /// The abbreviated mnemonic phrase. Each word can be abbreviated to
+        /// any prefix that uniquely identifies it.
+        #[clap(name = "PHRASE")]
+        phrase: String,
+    },
+}
+
+pub fn run(options: Options) -> Result<()> {
+    match options.op {
+        Op::LastWords { phrase } => {
+            let (language, words) = Language::split(&phrase)?;
+            for word in Mnemonic::complete(language, &words)? {
+                println!("{word}");
+            }
+        }
+        Op::Expand { phrase } => {
+            let (language, words) = Language::split(&phrase)?;
+            println!("{}", Mnemonic::expand(language, &words)?);
+        }
+    }
+    Ok(())
+}
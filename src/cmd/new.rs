@@ -1,17 +1,26 @@
 //! Module implementing the `new` subcommand for generating a mnemonic for a new
 //! hierarchical deterministic wallet.
 
-use crate::cmd::AccountOptions;
-use anyhow::{Context, Result};
-use clap::Parser;
+use crate::cmd::{self, AccountOptions};
+use anyhow::{ensure, Context, Result};
+use clap::{ArgGroup, Parser};
 use ethaddr::Address;
 use hdwallet::mnemonic::{Language, Mnemonic};
 use std::{
     fmt::{self, Display, Formatter},
+    num::NonZeroUsize,
+    path::PathBuf,
     str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
 };
 
 #[derive(Debug, Parser)]
+#[clap(group(
+    ArgGroup::new("vanity")
+        .multiple(true)
+        .args(["vanity_prefix", "vanity_suffix"])
+))]
 pub struct Options {
     /// The number of words for the mnemonic phrase.
     #[clap(short = 'n', long, default_value_t = 12)]
@@ -21,10 +30,31 @@ pub struct Options {
     #[clap(short, long, default_value_t)]
     language: Language,
 
-    /// Choose a vanity prefix for a public for the new mnemonic.
+    /// Path to a file containing the raw entropy bytes to generate the
+    /// mnemonic from instead of using the operating system's random number
+    /// generator. Use `-` for standard in. The entropy must be of high quality
+    /// and the file must be securely destroyed after use!
+    #[clap(long, conflicts_with = "vanity")]
+    entropy_file: Option<PathBuf>,
+
+    /// Choose a vanity prefix for a public for the new mnemonic. Note that the
+    /// search gets exponentially slower with the number of hex digits, with
+    /// each additional digit making it 16 times slower on average.
     #[clap(long)]
     vanity_prefix: Option<Prefix>,
 
+    /// Choose a vanity suffix for a public address for the new mnemonic. This
+    /// can be combined with "--vanity-prefix", in which case both must match.
+    /// Each additional hex digit makes the search 16 times slower on average.
+    #[clap(long)]
+    vanity_suffix: Option<Suffix>,
+
+    /// Require the vanity prefix and suffix to also match the casing of the
+    /// EIP-55 checksummed address. Each letter digit in the patterns makes the
+    /// search a further 2 times slower on average.
+    #[clap(long, requires = "vanity")]
+    vanity_checksum: bool,
+
     /// The password to use of the account whose private key should match the
     /// vanity prefix specifed in "--vanity-prefix".
     #[clap(long, default_value_t)]
@@ -40,12 +70,66 @@ pub struct Options {
     /// with the "--vanity-3account-index" option.
     #[clap(long, conflicts_with = "vanity_account_index")]
     vanity_hd_path: Option<String>,
+
+    /// The number of threads to use for searching for a vanity address.
+    /// Defaults to the number of available CPUs. A single job searches on the
+    /// main thread, which makes the search reproducible with deterministic
+    /// entropy.
+    #[clap(short, long, requires = "vanity")]
+    jobs: Option<NonZeroUsize>,
+}
+
+impl Options {
+    /// Returns whether or not an address matches the vanity prefix and suffix.
+    fn vanity_matches(&self, addr: Address) -> bool {
+        let (prefix, suffix) = (self.vanity_prefix.as_ref(), self.vanity_suffix.as_ref());
+        if !prefix.is_none_or(|prefix| prefix.matches(addr))
+            || !suffix.is_none_or(|suffix| suffix.matches(addr))
+        {
+            return false;
+        }
+        if !self.vanity_checksum {
+            return true;
+        }
+
+        let checksummed = addr.to_string();
+        let digits = &checksummed[2..];
+        prefix.is_none_or(|prefix| digits.starts_with(&prefix.digits))
+            && suffix.is_none_or(|suffix| digits.ends_with(&suffix.digits))
+    }
+
+    /// Verifies that the vanity prefix and suffix can match the same address.
+    fn verify_vanity(&self) -> Result<()> {
+        let (Some(prefix), Some(suffix)) = (&self.vanity_prefix, &self.vanity_suffix) else {
+            return Ok(());
+        };
+
+        // NOTE: A prefix and suffix that are together longer than an address
+        // overlap, in which case the overlapping digits need to agree.
+        let overlap = (prefix.digits.len() + suffix.digits.len()).saturating_sub(ADDRESS_DIGITS);
+        let (end, start) = (
+            &prefix.digits[prefix.digits.len() - overlap..],
+            &suffix.digits[..overlap],
+        );
+        let agree = if self.vanity_checksum {
+            end == start
+        } else {
+            end.eq_ignore_ascii_case(start)
+        };
+        ensure!(
+            agree,
+            "vanity prefix {prefix} and suffix {suffix} overlap with conflicting digits",
+        );
+        Ok(())
+    }
 }
 
+/// A vanity address prefix.
 #[derive(Clone, Debug)]
 struct Prefix {
     bytes: Vec<u8>,
     nibble: Option<u8>,
+    digits: String,
 }
 
 impl Prefix {
@@ -67,14 +151,7 @@ impl Prefix {
 
 impl Display for Prefix {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.write_str("0x")?;
-        for byte in &self.bytes {
-            write!(f, "{byte:02x}")?;
-        }
-        if let Some(nibble) = self.nibble {
-            write!(f, "{nibble:x}")?;
-        }
-        Ok(())
+        write!(f, "0x{}", self.digits)
     }
 }
 
@@ -82,43 +159,159 @@ impl FromStr for Prefix {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.strip_prefix("0x").context("missing '0x' prefix")?;
+        let digits = s.strip_prefix("0x").context("missing '0x' prefix")?;
+        let nibbles = parse_nibbles(digits)?;
 
-        let parse_nibble = |n: u8| match n {
-            b'0'..=b'9' => Ok(n - b'0'),
-            b'a'..=b'f' => Ok(n - b'a' + 0xa),
-            b'A'..=b'F' => Ok(n - b'a' + 0xa),
-            _ => anyhow::bail!("invalid hex digit {n:#x}"),
-        };
+        let mut chunks = nibbles.chunks_exact(2);
+        let bytes = chunks.by_ref().map(|c| (c[0] << 4) + c[1]).collect();
+        let nibble = chunks.remainder().first().copied();
 
-        let mut bytes = vec![0; s.len() / 2];
-        let mut nibble = None;
-        for (i, c) in s.as_bytes().chunks(2).enumerate() {
-            match c {
-                [hi, lo] => bytes[i] = (parse_nibble(*hi)? << 4) + parse_nibble(*lo)?,
-                [ni] => nibble = Some(parse_nibble(*ni)?),
-                _ => unreachable!(),
+        Ok(Self {
+            bytes,
+            nibble,
+            digits: digits.to_owned(),
+        })
+    }
+}
+
+/// A vanity address suffix.
+#[derive(Clone, Debug)]
+struct Suffix {
+    nibble: Option<u8>,
+    bytes: Vec<u8>,
+    digits: String,
+}
+
+impl Suffix {
+    fn matches(&self, addr: Address) -> bool {
+        let end = || addr.ends_with(&self.bytes);
+        let start = || {
+            if let Some(nibble) = self.nibble {
+                addr.len()
+                    .checked_sub(self.bytes.len() + 1)
+                    .map(|i| addr[i] & 0xf == nibble)
+                    .unwrap_or(false)
+            } else {
+                true
             }
-        }
+        };
+
+        end() && start()
+    }
+}
+
+impl Display for Suffix {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "0x{}", self.digits)
+    }
+}
+
+impl FromStr for Suffix {
+    type Err = anyhow::Error;
 
-        Ok(Self { bytes, nibble })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix("0x").context("missing '0x' prefix")?;
+        let nibbles = parse_nibbles(digits)?;
+
+        let mut chunks = nibbles.rchunks_exact(2);
+        let mut bytes = chunks
+            .by_ref()
+            .map(|c| (c[0] << 4) + c[1])
+            .collect::<Vec<_>>();
+        bytes.reverse();
+        let nibble = chunks.remainder().first().copied();
+
+        Ok(Self {
+            nibble,
+            bytes,
+            digits: digits.to_owned(),
+        })
     }
 }
 
+/// The number of hex digits in an address.
+const ADDRESS_DIGITS: usize = 40;
+
+/// Parses hex digits into their nibble values.
+fn parse_nibbles(digits: &str) -> Result<Vec<u8>> {
+    ensure!(
+        digits.len() <= ADDRESS_DIGITS,
+        "vanity pattern has more than {ADDRESS_DIGITS} hex digits",
+    );
+    digits
+        .chars()
+        .map(|c| {
+            let nibble = c
+                .to_digit(16)
+                .with_context(|| format!("invalid hex digit {c:?}"))?;
+            Ok(nibble as u8)
+        })
+        .collect()
+}
+
 pub fn run(options: Options) -> Result<()> {
     let random_mnemonic = || Mnemonic::random(options.language, options.length);
-    let mnemonic = if let Some(prefix) = options.vanity_prefix {
-        let mut account = AccountOptions {
-            mnemonic: random_mnemonic()?,
-            password: options.vanity_password,
-            account_index: options.vanity_account_index,
-            hd_path: options.vanity_hd_path,
+    let mnemonic = if let Some(entropy_file) = &options.entropy_file {
+        eprintln!(
+            "WARNING: Generating mnemonic from user-supplied entropy. The security \
+             of the wallet relies entirely on the quality of this entropy, make \
+             sure that it is truly random and that the entropy file is securely \
+             destroyed!"
+        );
+
+        let entropy = cmd::read_input(entropy_file)?;
+        let mnemonic = Mnemonic::from_entropy(options.language, &entropy)?;
+        ensure!(
+            mnemonic.mnemonic_length() == options.length,
+            "{} bytes of entropy yields a {}-word mnemonic, but {} words were requested",
+            entropy.len(),
+            mnemonic.mnemonic_length(),
+            options.length,
+        );
+
+        mnemonic
+    } else if options.vanity_prefix.is_some() || options.vanity_suffix.is_some() {
+        options.verify_vanity()?;
+        let jobs = options
+            .jobs
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get);
+
+        // NOTE: Every worker stops searching as soon as any of them finishes,
+        // either because it found a match or because it encountered an error.
+        let done = AtomicBool::new(false);
+        let search = || {
+            let result = vanity_search(&options, &done, random_mnemonic);
+            done.store(true, Ordering::Relaxed);
+            result
         };
-        while !prefix.matches(account.private_key()?.address()) {
-            account.mnemonic = random_mnemonic()?;
+        let (account, address) = if jobs == 1 {
+            search()?
+        } else {
+            thread::scope(|scope| {
+                let workers = (0..jobs).map(|_| scope.spawn(&search)).collect::<Vec<_>>();
+                let results = workers
+                    .into_iter()
+                    .map(|worker| worker.join().expect("vanity search worker panicked"))
+                    .collect::<Vec<_>>();
+
+                // NOTE: Prefer a match from any worker over errors from the
+                // others, and only fail if no worker found a match.
+                let mut error = None;
+                for result in results {
+                    match result {
+                        Ok(Some(found)) => return Ok(Some(found)),
+                        Ok(None) => {}
+                        Err(err) => error = error.or(Some(err)),
+                    }
+                }
+                error.map_or(Ok(None), Err)
+            })?
         }
+        .context("vanity search stopped without a match")?;
 
-        account.mnemonic
+        eprintln!("found vanity address {address} at {}", account.hd_path()?);
+        account.mnemonic.expect("mnemonic is always set")
     } else {
         random_mnemonic()?
     };
@@ -126,3 +319,31 @@ pub fn run(options: Options) -> Result<()> {
     println!("{mnemonic}");
     Ok(())
 }
+
+/// Searches for an account matching the vanity prefix and suffix with randomly
+/// generated mnemonics, until a match is found or the search is done.
+fn vanity_search(
+    options: &Options,
+    done: &AtomicBool,
+    random_mnemonic: impl Fn() -> Result<Mnemonic>,
+) -> Result<Option<(AccountOptions, Address)>> {
+    let mut account = AccountOptions {
+        mnemonic: None,
+        mnemonic_file: None,
+        password: options.vanity_password.clone(),
+        prompt_password: false,
+        account_index: options.vanity_account_index,
+        coin_type: 60,
+        account: 0,
+        change: 0,
+        hd_path: options.vanity_hd_path.clone(),
+    };
+    while !done.load(Ordering::Relaxed) {
+        account.mnemonic = Some(random_mnemonic()?);
+        let address = account.private_key()?.address();
+        if options.vanity_matches(address) {
+            return Ok(Some((account, address)));
+        }
+    }
+    Ok(None)
+}
@@ -1,27 +1,29 @@
-//! Module implementing the `address` subcommand for displaying the public
-//! address for corresponding account.
+//! Module implementing the `public-key` subcommand for displaying the public
+//! key for corresponding account.
 
-use crate::cmd::AccountOptions;
+use crate::cmd::{AccountOptions, OutputFormat};
 use anyhow::Result;
 use clap::Parser;
+use serde_json::json;
 
 #[derive(Debug, Parser)]
 pub struct Options {
     #[clap(flatten)]
     account: AccountOptions,
+
+    /// Output the 33-byte SEC1 compressed public key instead of the 65-byte
+    /// uncompressed public key.
+    #[clap(long)]
+    compressed: bool,
 }
 
-pub fn run(options: Options) -> Result<()> {
-    println!(
-        "0x{}",
-        hex::encode(
-            options
-                .account
-                .private_key()?
-                .public()
-                .encode_uncompressed()
-                .as_slice()
-        )
-    );
+pub fn run(options: Options, output: OutputFormat) -> Result<()> {
+    let public = options.account.private_key()?.public();
+    let public_key = if options.compressed {
+        format!("0x{}", hex::encode(public.encode_compressed()))
+    } else {
+        format!("0x{}", hex::encode(public.encode_uncompressed()))
+    };
+    output.print(&public_key, json!({ "publicKey": public_key }));
     Ok(())
 }
@@ -0,0 +1,51 @@
+//! Module implementing the `recover` subcommand for recovering the signer of
+//! an ECDSA signature.
+
+use crate::cmd::{self, OutputFormat};
+use anyhow::Result;
+use clap::Parser;
+use hdwallet::{account::Signature, message::EthereumMessage};
+use serde_json::json;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Options {
+    #[clap(subcommand)]
+    input: Input,
+
+    /// The signature to recover the signer of. The V-value can be the raw
+    /// y-parity, 27 or 28 as used by "eth_sign", or an EIP-155 V-value with
+    /// chain replay protection.
+    #[clap(short, long)]
+    signature: Signature,
+}
+
+#[derive(Debug, Parser)]
+enum Input {
+    /// Recover the signer of an Ethereum message signature.
+    Message {
+        /// Path to the message signed in the "eth_sign" scheme. Use `-` for
+        /// standard in.
+        #[clap(name = "MESSAGE")]
+        message: PathBuf,
+
+        /// Decode the message contents as a hexadecimal string, like
+        /// "personal_sign" requests with hex payloads. Otherwise, the raw
+        /// bytes of the message are used.
+        #[clap(long)]
+        hex: bool,
+    },
+}
+
+pub fn run(options: Options, output: OutputFormat) -> Result<()> {
+    let message = match options.input {
+        Input::Message { message, hex } => {
+            let message = cmd::decode_message(cmd::read_input(&message)?, hex)?;
+            EthereumMessage(message).signing_message()
+        }
+    };
+
+    let address = options.signature.recover_address(message)?.to_string();
+    output.print(&address, json!({ "address": address }));
+    Ok(())
+}
@@ -1,14 +1,24 @@
 //! Module implementing the `sign` subcommand for generating ECDSA signatures.
 
-use crate::cmd::{self, AccountOptions};
+use crate::cmd::{self, AccountOptions, OutputFormat};
 use anyhow::{ensure, Context as _, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use ethaddr::Address;
+use ethnum::U256;
 use hdwallet::{
-    message::EthereumMessage,
-    transaction::{LegacyTransaction, Transaction},
+    account::Signature,
+    message::{EthereumMessage, ValidatorMessage},
+    transaction::{
+        accesslist::{AccessList, StorageSlot},
+        LegacyTransaction, Transaction,
+    },
     typeddata::TypedData,
 };
-use std::{convert::TryInto, path::PathBuf};
+use serde_json::{json, Value};
+use std::{
+    path::{Path, PathBuf},
+    str,
+};
 
 #[derive(Debug, Parser)]
 pub struct Options {
@@ -17,6 +27,60 @@ pub struct Options {
 
     #[clap(flatten)]
     account: AccountOptions,
+
+    /// The format of the `v` byte of the printed signature.
+    #[clap(long, global = true, value_enum, default_value_t = VFormat::Legacy)]
+    v_format: VFormat,
+
+    /// The chain ID to use for EIP-155 adjusted `v` bytes. When signing
+    /// transactions, this defaults to the transaction's chain ID.
+    #[clap(long, global = true)]
+    chain_id: Option<u64>,
+
+    /// Accept JSON input files with "//" and "/* */" comments and trailing
+    /// commas, for hand-edited transactions and typed data.
+    #[clap(long, global = true)]
+    json5: bool,
+}
+
+/// The format for the `v` byte of a signature.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum VFormat {
+    /// EIP-155 adjusted `v` byte, computed as `{0,1} + chain_id * 2 + 35`.
+    Eip155,
+    /// Legacy `v` byte of either 27 or 28, as used by "eth_sign".
+    Legacy,
+    /// Raw y-parity of the signature, either 0 or 1.
+    Raw,
+}
+
+impl VFormat {
+    /// Formats a signature with the `v` byte in the current format, returning
+    /// both its hex string and JSON object representations.
+    fn format(self, signature: &Signature, chain_id: Option<u64>) -> Result<(String, Value)> {
+        let v = match self {
+            VFormat::Eip155 => {
+                let chain_id = chain_id.context(
+                    "EIP-155 `v` format requires a chain ID, specify one with `--chain-id`",
+                )?;
+                signature.v(Some(U256::from(chain_id)))
+            }
+            VFormat::Legacy => signature.v(None),
+            VFormat::Raw => signature.y_parity(),
+        };
+        let v = u8::try_from(v).with_context(|| {
+            format!("`v` value {v} does not fit in a single byte, use a different `--v-format`")
+        })?;
+        let hex = format!("0x{:064x}{:064x}{v:02x}", signature.r(), signature.s());
+
+        let json = json!({
+            "signature": hex,
+            "r": format!("0x{:064x}", signature.r()),
+            "s": format!("0x{:064x}", signature.s()),
+            "v": v,
+        });
+        Ok((hex, json))
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -36,15 +100,67 @@ enum Input {
         /// protection. Use this care!
         #[clap(long)]
         allow_missing_relay_protection: bool,
+
+        /// Force allowing transactions that send Ether to the zero address.
+        /// This is usually a mistake that results in lost funds, so only use
+        /// this if burning funds is intentional!
+        #[clap(long)]
+        allow_burn: bool,
+
+        /// Force allowing transactions with a chain ID of zero. These are not
+        /// accepted by any public network, so this is usually a mistake.
+        #[clap(long)]
+        allow_zero_chain_id: bool,
+
+        /// Force allowing transactions with a gas limit below their intrinsic
+        /// gas (21000 for simple transfers, and more for contract creations,
+        /// calldata and access lists). Such transactions can never execute.
+        #[clap(long)]
+        allow_low_gas: bool,
+
+        /// Additional access list entry for the transaction, specified as an
+        /// address followed by comma-separated storage slots (for example
+        /// "0x1111111111111111111111111111111111111111:0x1,0x2"). This option
+        /// can be specified multiple times, and the resulting access list is
+        /// normalized by merging duplicate addresses and sorting storage slots.
+        #[clap(long = "access", value_parser = AccessList::parse_entry)]
+        access: Vec<(Address, Vec<StorageSlot>)>,
+
+        /// Print the address and HD path of the account that signed the
+        /// transaction to standard error. The address is recovered from the
+        /// signed transaction, so it is exactly the sender that the network
+        /// will see.
+        #[clap(short, long)]
+        verbose: bool,
     },
 
     /// Sign an Ethereum message.
     Message {
-        /// Path to the message to sign in the "eth_sign" scheme. This message
-        /// will be prefixed with "\x19Ethereum Signed Message:\n" and its
-        /// length before hashing and singing.
-        #[clap(name = "MESSAGE")]
-        message: PathBuf,
+        /// Paths to the messages to sign in the "eth_sign" scheme. These
+        /// messages will be prefixed with "\x19Ethereum Signed Message:\n" and
+        /// their length before hashing and singing. When multiple messages are
+        /// specified, each signature is labeled with its message path.
+        #[clap(name = "MESSAGE", required = true)]
+        messages: Vec<PathBuf>,
+
+        /// Decode the message contents as a hexadecimal string before signing,
+        /// like "personal_sign" requests with hex payloads. Otherwise, the raw
+        /// bytes of the message are signed.
+        #[clap(long)]
+        hex: bool,
+    },
+
+    /// Sign data with an intended validator (EIP-191 version 0x00).
+    Validator {
+        /// Path to the data to sign. The data is prefixed with "\x19\x00" and
+        /// the validator address before hashing and signing.
+        #[clap(name = "DATA")]
+        data: PathBuf,
+
+        /// The address of the intended validator, usually the contract that
+        /// verifies the signature.
+        #[clap(long)]
+        validator: Address,
     },
 
     /// Sign EIP-712 typed data.
@@ -53,26 +169,79 @@ enum Input {
         /// Path to the EIP-712 typed data in JSON format.
         #[clap(name = "TYPEDDATA")]
         typed_data: PathBuf,
+
+        /// Instead of signing the typed data locally, output the JSON-RPC
+        /// parameters array for an "eth_signTypedData_v4" request for the
+        /// account. This can be forwarded to a remote signer, such as a node or
+        /// wallet with the account's key.
+        #[clap(long)]
+        rpc_params: bool,
+
+        /// Additionally print the R, S and V components of the signature on
+        /// separate lines, for embedding into contract calls.
+        #[clap(long, conflicts_with = "rpc_params")]
+        split: bool,
     },
 
     /// Sign a raw data.
     Raw {
-        /// The 32 byte message to sign specified as a hexadecimal string.
-        #[clap(name = "BYTES", value_parser = permissive_hex_digest)]
-        message: [u8; 32],
+        /// The 32 byte message to sign specified as a hexadecimal string. Use
+        /// `-` to read the hexadecimal string from standard in.
+        #[clap(name = "BYTES", value_parser = raw_message)]
+        message: RawMessage,
+
+        /// Read the message from standard in as exactly 32 raw bytes instead
+        /// of a hexadecimal string.
+        #[clap(long)]
+        binary: bool,
     },
 }
 
-pub fn run(options: Options) -> Result<()> {
+/// A raw 32 byte message to sign.
+#[derive(Clone, Debug)]
+enum RawMessage {
+    /// The message is read from standard in.
+    Stdin,
+    /// The message specified on the command line.
+    Digest([u8; 32]),
+}
+
+/// Parses a raw message argument, with `-` signifying standard in.
+fn raw_message(s: &str) -> Result<RawMessage> {
+    match s {
+        "-" => Ok(RawMessage::Stdin),
+        s => Ok(RawMessage::Digest(cmd::permissive_hex_digest(s)?)),
+    }
+}
+
+pub fn run(options: Options, output: OutputFormat) -> Result<()> {
     let account = options.account.private_key()?;
+    let format = |signature: &Signature| options.v_format.format(signature, options.chain_id);
+    let print = |signature: &Signature| -> Result<()> {
+        let (hex, json) = format(signature)?;
+        output.print(hex, json);
+        Ok(())
+    };
     match options.input {
         Input::Transaction {
             transaction,
             signature_only,
             allow_missing_relay_protection,
+            allow_burn,
+            allow_zero_chain_id,
+            allow_low_gas,
+            access,
+            verbose,
         } => {
-            let transaction =
-                serde_json::from_slice::<Transaction>(&cmd::read_input(&transaction)?)?;
+            let input = cmd::read_json_input(&transaction, options.json5)?;
+            let mut transaction = serde_json::from_slice::<Transaction>(&input)?;
+            if !access.is_empty() {
+                let access_list = transaction
+                    .access_list_mut()
+                    .context("legacy transactions do not support access lists")?;
+                access_list.0.extend(access);
+                access_list.normalize();
+            }
             if let Transaction::Legacy(LegacyTransaction { chain_id: None, .. }) = &transaction {
                 ensure!(
                     allow_missing_relay_protection,
@@ -80,30 +249,104 @@ pub fn run(options: Options) -> Result<()> {
                      Use `--allow-missing-relay-protection` if this was intentional.",
                 );
             }
+            ensure!(
+                allow_zero_chain_id || !transaction.is_zero_chain_id(),
+                "Signed transaction with a chain ID of zero. \
+                 Use `--allow-zero-chain-id` if this was intentional.",
+            );
+            ensure!(
+                allow_low_gas || !transaction.is_below_intrinsic_gas(),
+                "Signed transaction with gas limit {} below its intrinsic gas {}. \
+                 Use `--allow-low-gas` if this was intentional.",
+                transaction.gas(),
+                transaction.intrinsic_gas(),
+            );
+            ensure!(
+                allow_burn || !transaction.is_zero_address_transfer(),
+                "Signed transaction sending {} Wei to the zero address. \
+                 Use `--allow-burn` if this was intentional.",
+                transaction.value(),
+            );
             let signature = account.sign(transaction.signing_message());
+            if verbose {
+                eprintln!(
+                    "signed by {} ({})",
+                    transaction.recover_signer(&signature)?,
+                    options.account.hd_path()?,
+                );
+            }
             if signature_only {
-                println!("{signature}");
+                let chain_id = options.chain_id.or_else(|| {
+                    transaction
+                        .chain_id()
+                        .and_then(|chain_id| u64::try_from(chain_id).ok())
+                });
+                let (hex, json) = options.v_format.format(&signature, chain_id)?;
+                output.print(hex, json);
             } else {
-                println!("0x{}", hex::encode(transaction.encode(signature)));
+                let transaction = format!("0x{}", hex::encode(transaction.encode(signature)));
+                output.print(&transaction, json!({ "transaction": transaction }));
             }
         }
-        Input::Message { message } => {
-            let message = EthereumMessage(cmd::read_input(&message)?);
-            println!("{}", account.sign(message.signing_message()));
+        Input::Message { messages, hex } => {
+            cmd::for_each_input(&messages, output, |message| {
+                let message = cmd::decode_message(message, hex)?;
+                format(&account.sign(EthereumMessage(message).signing_message()))
+            })?;
         }
-        Input::TypedData { typed_data } => {
-            let typed_data = serde_json::from_slice::<TypedData>(&cmd::read_input(&typed_data)?)?;
-            println!("{}", account.sign(typed_data.signing_message()));
+        Input::Validator { data, validator } => {
+            let message = ValidatorMessage {
+                validator,
+                data: cmd::read_input(&data)?,
+            };
+            print(&account.sign(message.signing_message()))?;
         }
-        Input::Raw { message } => {
-            println!("{}", account.sign(message));
+        Input::TypedData {
+            typed_data,
+            rpc_params,
+            split,
+        } => {
+            let input = cmd::read_json_input(&typed_data, options.json5)?;
+            let typed_data = serde_json::from_slice::<TypedData>(&input)?;
+            if rpc_params {
+                // NOTE: The typed data document is passed as a JSON encoded
+                // string, as expected by most wallet implementations.
+                let document = serde_json::from_slice::<Value>(&input)?;
+                let params = json!([account.address().to_string(), document.to_string()]);
+                println!("{params}");
+            } else if split && output == OutputFormat::Text {
+                let signature = account.sign(typed_data.signing_message());
+                let (hex, json) = format(&signature)?;
+                println!("{hex}");
+                println!("r: 0x{:064x}", signature.r());
+                println!("s: 0x{:064x}", signature.s());
+                println!("v: {}", json["v"]);
+            } else {
+                print(&account.sign(typed_data.signing_message()))?;
+            }
+        }
+        Input::Raw { message, binary } => {
+            let message = match message {
+                RawMessage::Stdin => {
+                    let input = cmd::read_input(Path::new("-"))?;
+                    if binary {
+                        input[..]
+                            .try_into()
+                            .context("binary message for signing must be exactly 32 bytes long")?
+                    } else {
+                        cmd::permissive_hex_digest(str::from_utf8(&input)?)?
+                    }
+                }
+                RawMessage::Digest(digest) => {
+                    ensure!(
+                        !binary,
+                        "`--binary` requires reading the message from standard in"
+                    );
+                    digest
+                }
+            };
+            print(&account.sign(message))?;
         }
     }
     Ok(())
 }
-
-fn permissive_hex_digest(s: &str) -> Result<[u8; 32]> {
-    cmd::permissive_hex(s)?[..]
-        .try_into()
-        .context("message for signing must be exactly 32 bytes long")
-}
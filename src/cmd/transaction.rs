@@ -0,0 +1,120 @@
+//! Module implementing the `transaction` subcommand for inspecting Ethereum
+//! transactions without signing them.
+
+use crate::cmd;
+use anyhow::{ensure, Result};
+use clap::Parser;
+use hdwallet::transaction::{LegacyTransaction, Transaction};
+use serde_json::Value;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Options {
+    #[clap(subcommand)]
+    op: Op,
+}
+
+#[derive(Debug, Parser)]
+enum Op {
+    /// Validate a transaction for common problems without signing it.
+    Validate {
+        /// Path to the transaction to validate in JSON format. Use `-` for
+        /// standard in.
+        #[clap(name = "TRANSACTION")]
+        transaction: PathBuf,
+    },
+}
+
+pub fn run(options: Options) -> Result<()> {
+    match options.op {
+        Op::Validate { transaction } => {
+            let json = serde_json::from_slice::<Value>(&cmd::read_input(&transaction)?)?;
+            let report = validate(&json)?;
+            for warning in &report.warnings {
+                println!("warning: {warning}");
+            }
+            for error in &report.errors {
+                println!("error: {error}");
+            }
+            ensure!(
+                report.errors.is_empty(),
+                "transaction failed validation with {} error(s)",
+                report.errors.len(),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// A transaction validation report.
+#[derive(Default)]
+struct Report {
+    warnings: Vec<String>,
+    errors: Vec<String>,
+}
+
+/// Validates a JSON transaction. The raw JSON value is needed in order to
+/// verify the casing of the `to` address.
+fn validate(json: &Value) -> Result<Report> {
+    let transaction = serde_json::from_value::<Transaction>(json.clone())?;
+    let mut report = Report::default();
+
+    if let Transaction::Legacy(LegacyTransaction { chain_id: None, .. }) = &transaction {
+        report
+            .warnings
+            .push("legacy transaction without chain ID for relay protection".to_string());
+    }
+
+    if transaction.is_zero_chain_id() {
+        report
+            .warnings
+            .push("transaction with a chain ID of zero".to_string());
+    }
+
+    let fees = match &transaction {
+        Transaction::Eip1559(tx) => Some((tx.max_priority_fee_per_gas, tx.max_fee_per_gas)),
+        Transaction::Eip4844(tx) => Some((tx.max_priority_fee_per_gas, tx.max_fee_per_gas)),
+        Transaction::Eip7702(tx) => Some((tx.max_priority_fee_per_gas, tx.max_fee_per_gas)),
+        _ => None,
+    };
+    if let Some((max_priority_fee_per_gas, max_fee_per_gas)) = fees {
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            report.errors.push(format!(
+                "max priority fee per gas {max_priority_fee_per_gas} exceeds max fee per gas \
+                 {max_fee_per_gas}",
+            ));
+        }
+    }
+
+    if transaction.is_zero_address_transfer() {
+        report.warnings.push(format!(
+            "transaction sends {} Wei to the zero address",
+            transaction.value(),
+        ));
+    }
+
+    let intrinsic_gas = transaction.intrinsic_gas();
+    if transaction.gas() < intrinsic_gas {
+        report.errors.push(format!(
+            "gas limit {} is below the intrinsic gas {intrinsic_gas}",
+            transaction.gas(),
+        ));
+    }
+
+    if let (Some(raw), Some(to)) = (json.get("to").and_then(Value::as_str), transaction.to()) {
+        if raw != to.to_string() {
+            let digits = raw.trim_start_matches("0x");
+            if digits == digits.to_lowercase() || digits == digits.to_uppercase() {
+                report
+                    .warnings
+                    .push(format!("target address {raw} is not EIP-55 checksummed"));
+            } else {
+                report.errors.push(format!(
+                    "target address {raw} has an invalid EIP-55 checksum"
+                ));
+            }
+        }
+    }
+
+    Ok(report)
+}
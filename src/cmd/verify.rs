@@ -0,0 +1,87 @@
+//! Module implementing the `verify` subcommand for verifying ECDSA signatures.
+
+use crate::cmd;
+use anyhow::{ensure, Result};
+use clap::Parser;
+use ethaddr::Address;
+use hdwallet::{
+    account::Signature, message::EthereumMessage, transaction::Transaction, typeddata::TypedData,
+};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Options {
+    #[clap(subcommand)]
+    input: Input,
+
+    /// The signature to verify. The V-value can be the raw y-parity, 27 or 28
+    /// as used by "eth_sign", or an EIP-155 V-value with chain replay
+    /// protection.
+    #[clap(short, long)]
+    signature: Signature,
+
+    /// The address of the account that is expected to have signed.
+    #[clap(short, long)]
+    address: Address,
+}
+
+#[derive(Debug, Parser)]
+enum Input {
+    /// Verify an Ethereum transaction signature.
+    Transaction {
+        /// Path to the signed transaction in JSON format.
+        #[clap(name = "TRANSACTION")]
+        transaction: PathBuf,
+    },
+
+    /// Verify an Ethereum message signature.
+    Message {
+        /// Path to the message signed in the "eth_sign" scheme.
+        #[clap(name = "MESSAGE")]
+        message: PathBuf,
+    },
+
+    /// Verify an EIP-712 typed data signature.
+    #[clap(name = "typeddata")]
+    TypedData {
+        /// Path to the signed EIP-712 typed data in JSON format.
+        #[clap(name = "TYPEDDATA")]
+        typed_data: PathBuf,
+    },
+
+    /// Verify a raw data signature.
+    Raw {
+        /// The signed 32 byte message specified as a hexadecimal string.
+        #[clap(name = "BYTES", value_parser = cmd::permissive_hex_digest)]
+        message: [u8; 32],
+    },
+}
+
+pub fn run(options: Options) -> Result<()> {
+    let message = match options.input {
+        Input::Transaction { transaction } => {
+            let transaction =
+                serde_json::from_slice::<Transaction>(&cmd::read_input(&transaction)?)?;
+            transaction.signing_message()
+        }
+        Input::Message { message } => {
+            let message = EthereumMessage(cmd::read_input(&message)?);
+            message.signing_message()
+        }
+        Input::TypedData { typed_data } => {
+            let typed_data = serde_json::from_slice::<TypedData>(&cmd::read_input(&typed_data)?)?;
+            typed_data.signing_message()
+        }
+        Input::Raw { message } => message.into(),
+    };
+
+    let signer = options.signature.recover_address(message)?;
+    ensure!(
+        signer == options.address,
+        "signature was signed by {signer} and not {}",
+        options.address,
+    );
+
+    println!("{signer}");
+    Ok(())
+}
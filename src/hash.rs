@@ -1,18 +1,270 @@
 //! Module implementing various hashing utilities.
 
+use crate::serialization;
+use anyhow::Result;
+use serde::Deserialize;
 use sha2::{Digest as _, Sha256};
 use sha3::Keccak256;
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    io::{self, Write},
+    ops::Deref,
+    str::FromStr,
+};
+
+/// A 32-byte hash.
+#[derive(Clone, Copy, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct Hash32(#[serde(with = "serialization::bytearray")] pub [u8; 32]);
+
+impl AsRef<[u8]> for Hash32 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8; 32]> for Hash32 {
+    fn as_ref(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Deref for Hash32 {
+    type Target = [u8; 32];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for Hash32 {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Hash32> for [u8; 32] {
+    fn from(hash: Hash32) -> Self {
+        hash.0
+    }
+}
+
+impl PartialEq<[u8; 32]> for Hash32 {
+    fn eq(&self, other: &[u8; 32]) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Debug for Hash32 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Hash32")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
+impl Display for Hash32 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl FromStr for Hash32 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut hash = [0; 32];
+        hex::decode_to_slice(s.strip_prefix("0x").unwrap_or(s), &mut hash)?;
+        Ok(Self(hash))
+    }
+}
 
 /// Returns the Keccak-256 hash of the specified input.
-pub fn keccak256(data: impl AsRef<[u8]>) -> [u8; 32] {
+///
+/// The hash is computed with the `sha3` crate by default, or with the
+/// `tiny-keccak` crate when the feature of the same name is enabled. Use
+/// [`Keccak256Hasher`] for data that is not already in memory.
+pub fn keccak256(data: impl AsRef<[u8]>) -> Hash32 {
+    #[cfg(not(feature = "tiny-keccak"))]
+    let hash = sha3_keccak256(data.as_ref());
+    #[cfg(feature = "tiny-keccak")]
+    let hash = tiny_keccak256(data.as_ref());
+    Hash32(hash)
+}
+
+/// Keccak-256 implementation backed by the `sha3` crate.
+#[cfg_attr(feature = "tiny-keccak", allow(dead_code))]
+fn sha3_keccak256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Keccak256::new();
-    hasher.update(data.as_ref());
+    hasher.update(data);
     hasher.finalize().into()
 }
 
+/// Keccak-256 implementation backed by the `tiny-keccak` crate.
+#[cfg(feature = "tiny-keccak")]
+fn tiny_keccak256(data: &[u8]) -> [u8; 32] {
+    use tiny_keccak::{Hasher as _, Keccak};
+
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut hash = [0; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Returns the Keccak-256 hash of the concatenation of the specified parts,
+/// without allocating an intermediate buffer for the concatenated data.
+pub fn keccak256_parts(parts: &[&[u8]]) -> Hash32 {
+    let mut hasher = Keccak256Hasher::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    Hash32(hasher.finalize())
+}
+
+/// An incremental Keccak-256 hasher, for hashing data in chunks without
+/// buffering all of it in memory.
+///
+/// The hash is computed with the `sha3` crate by default, or with the
+/// `tiny-keccak` crate when the feature of the same name is enabled.
+pub struct Keccak256Hasher {
+    #[cfg(not(feature = "tiny-keccak"))]
+    inner: Keccak256,
+    #[cfg(feature = "tiny-keccak")]
+    inner: tiny_keccak::Keccak,
+}
+
+impl Keccak256Hasher {
+    /// Creates a new hasher.
+    pub fn new() -> Self {
+        Self {
+            #[cfg(not(feature = "tiny-keccak"))]
+            inner: Keccak256::new(),
+            #[cfg(feature = "tiny-keccak")]
+            inner: tiny_keccak::Keccak::v256(),
+        }
+    }
+
+    /// Feeds more data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        #[cfg(feature = "tiny-keccak")]
+        use tiny_keccak::Hasher as _;
+
+        self.inner.update(data);
+    }
+
+    /// Returns the Keccak-256 hash of all the data fed into the hasher.
+    pub fn finalize(self) -> [u8; 32] {
+        #[cfg(not(feature = "tiny-keccak"))]
+        {
+            self.inner.finalize().into()
+        }
+        #[cfg(feature = "tiny-keccak")]
+        {
+            use tiny_keccak::Hasher as _;
+
+            let mut hash = [0; 32];
+            self.inner.finalize(&mut hash);
+            hash
+        }
+    }
+}
+
+impl Default for Keccak256Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for Keccak256Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Returns the SHA256 hash of the specified input.
-pub fn sha256(data: impl AsRef<[u8]>) -> [u8; 32] {
+pub fn sha256(data: impl AsRef<[u8]>) -> Hash32 {
     let mut hasher = Sha256::new();
     hasher.update(data.as_ref());
-    hasher.finalize().into()
+    Hash32(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn keccak256_vectors() {
+        for (data, hash) in [
+            (
+                &b""[..],
+                hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+            ),
+            (
+                &b"hello world"[..],
+                hex!("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad"),
+            ),
+            (
+                &[0xff; 200][..],
+                hex!("3e04329b5f5c0f493dd965957722717ef46ed487733d5f9da932de72d9ac4a51"),
+            ),
+        ] {
+            assert_eq!(keccak256(data), hash);
+            assert_eq!(sha3_keccak256(data), hash);
+            #[cfg(feature = "tiny-keccak")]
+            assert_eq!(tiny_keccak256(data), hash);
+
+            let mut hasher = Keccak256Hasher::new();
+            for chunk in data.chunks(7) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), hash);
+
+            let mut hasher = Keccak256Hasher::default();
+            io::copy(&mut &data[..], &mut hasher).unwrap();
+            assert_eq!(hasher.finalize(), hash);
+        }
+    }
+
+    #[test]
+    fn keccak256_parts_matches_concatenation() {
+        assert_eq!(
+            keccak256_parts(&[b"hello", b"", b" ", b"world"]),
+            keccak256(b"hello world"),
+        );
+        assert_eq!(keccak256_parts(&[]), keccak256(b""));
+    }
+
+    #[test]
+    fn hash_to_string() {
+        assert_eq!(
+            keccak256(b"").to_string(),
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        );
+    }
+
+    #[test]
+    fn hash_from_str() {
+        let hash = keccak256(b"hello world!");
+        assert_eq!(hash.to_string().parse::<Hash32>().unwrap(), hash);
+        assert_eq!(hash.to_string()[2..].parse::<Hash32>().unwrap(), hash);
+        assert!("0x1234".parse::<Hash32>().is_err());
+    }
+
+    #[test]
+    fn wraps_existing_hashes() {
+        let bytes = hex!("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad");
+        let hash = Hash32::from(bytes);
+        assert_eq!(hash, keccak256(b"hello world"));
+        assert_eq!(hash, bytes);
+        assert_eq!(*hash, bytes);
+        assert_eq!(<[u8; 32]>::from(hash), bytes);
+        assert_eq!(AsRef::<[u8]>::as_ref(&hash), &bytes[..]);
+    }
 }
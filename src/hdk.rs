@@ -1,65 +1,138 @@
 //! Module implementing the hierachical deterministic key derivation scheme.
 
+mod extended;
 mod path;
 
-pub use self::path::{Component, Path};
+pub use self::{
+    extended::ExtendedKey,
+    path::{Component, Path},
+};
 use crate::account::PrivateKey;
-use anyhow::{Context as _, Result};
-use hmac::{Hmac, Mac as _};
-use k256::{elliptic_curve::sec1::ToEncodedPoint as _, SecretKey};
-use sha2::Sha512;
+use anyhow::{bail, ensure, Context as _, Result};
+use k256::SecretKey;
+use std::ops::Range;
 
 /// A value indicating a path component is hardened.
 const HARDENED: u32 = 0x8000_0000;
 
 /// Creates a new extended private key from a seed.
 pub fn derive(seed: impl AsRef<[u8]>, path: &Path) -> Result<PrivateKey> {
-    derive_slice(seed.as_ref(), path)
+    ExtendedKey::master(seed)?.derive(path)?.private_key()
 }
 
-fn derive_slice(seed: &[u8], path: &Path) -> Result<PrivateKey> {
-    let mut extended_key = {
-        let mut hmac = Hmac::<Sha512>::new_from_slice(b"Bitcoin seed")?;
-        hmac.update(seed.as_ref());
-        hmac.finalize().into_bytes()
-    };
+/// Derives the private key for the specified account index using the default
+/// Ethereum HD path `m/44'/60'/0'/0/{index}`.
+pub fn derive_index(seed: impl AsRef<[u8]>, index: usize) -> Result<PrivateKey> {
+    derive(seed, &Path::for_index(index)?)
+}
 
-    for (i, component) in path.components().enumerate() {
-        let (secret, chain_code) = extended_key.split_at(32);
-        let secret = SecretKey::from_slice(secret)?;
-
-        let mut hmac: Hmac<Sha512> = Hmac::<Sha512>::new_from_slice(chain_code)?;
-        let value = match component {
-            Component::Hardened(value) => {
-                hmac.update(&[0]);
-                hmac.update(&secret.to_bytes());
-                value | HARDENED
-            }
-            Component::Normal(value) => {
-                hmac.update(secret.public_key().to_encoded_point(true).as_bytes());
-                value
-            }
-        };
-        hmac.update(&value.to_be_bytes());
+/// Derives the private keys for a range of indices, appended as a final normal
+/// component to the base path. The base path is only derived once, so only the
+/// last component is derived for each index.
+pub fn derive_range(
+    seed: impl AsRef<[u8]>,
+    base_path: &Path,
+    indices: Range<u32>,
+) -> Result<impl Iterator<Item = Result<PrivateKey>>> {
+    let derivation = Derivation::with_parent(seed, base_path)?;
+    Ok(indices.map(move |index| derivation.child(index)))
+}
+
+/// A cached derivation for sibling account keys under a common parent node,
+/// by default the Ethereum HD path `m/44'/60'/0'/0`.
+///
+/// This holds the extended key for the parent node, so that deriving a key for
+/// an account index only requires a single child key derivation step instead
+/// of deriving the full path from the seed. This is much faster when deriving
+/// many account keys.
+pub struct Derivation {
+    parent: ExtendedKey,
+    path: String,
+}
+
+impl Derivation {
+    /// Creates a new cached derivation for the specified seed.
+    pub fn new(seed: impl AsRef<[u8]>) -> Result<Self> {
+        Self::bip44(seed, 60, 0, 0)
+    }
+
+    /// Creates a new cached derivation for sibling account keys under the
+    /// BIP-0044 path `m/44'/{coin_type}'/{account}'/{change}`.
+    pub fn bip44(
+        seed: impl AsRef<[u8]>,
+        coin_type: u32,
+        account: u32,
+        change: u32,
+    ) -> Result<Self> {
+        let path = format!("m/44'/{coin_type}'/{account}'/{change}").parse()?;
+        Self::with_parent(seed, &path)
+    }
 
-        let mut child_key = hmac.finalize().into_bytes();
+    /// Creates a new cached derivation for sibling account keys under the
+    /// specified parent path.
+    pub fn with_parent(seed: impl AsRef<[u8]>, parent: &Path) -> Result<Self> {
+        Ok(Self {
+            parent: ExtendedKey::master(seed)?.derive(parent)?,
+            path: parent.to_string(),
+        })
+    }
 
-        let child_secret = SecretKey::from_slice(&child_key[..32])
-            .with_context(|| format!("path '{path}' component #{i} yields invalid child key"))?;
-        let next_secret =
-            SecretKey::new(*child_secret.as_scalar_primitive() + *secret.as_scalar_primitive());
-        child_key[..32].copy_from_slice(&next_secret.to_bytes());
+    /// Derives the private key for the specified account index, equivalent to
+    /// deriving the path `{parent}/{index}` from the seed, for example
+    /// `m/44'/60'/0'/0/{index}` for the default Ethereum HD path.
+    pub fn child(&self, index: u32) -> Result<PrivateKey> {
+        let path = || format!("{}/{index}", self.path);
+        ensure!(
+            index < HARDENED,
+            "invalid BIP-0032 path '{}': component '{index}' out of range",
+            path(),
+        );
+        self.parent
+            .derive_child(Component::Normal(index))
+            .with_context(|| format!("path '{}' yields no valid child key", path()))?
+            .private_key()
+    }
+}
 
-        extended_key = child_key
+/// Adds a child secret to its parent. Returns `None` if the resulting secret
+/// is zero, and therefore an invalid key.
+fn add_secrets(child: &SecretKey, parent: &SecretKey) -> Option<SecretKey> {
+    let sum = *child.as_scalar_primitive() + *parent.as_scalar_primitive();
+    if bool::from(sum.is_zero()) {
+        return None;
     }
+    Some(SecretKey::new(sum))
+}
 
-    PrivateKey::new(&extended_key[..32])
+/// Derives the first valid child key starting at the specified component.
+///
+/// BIP-0032 specifies that, in the (astronomically unlikely) event that a
+/// derived child key is invalid, one should proceed with the next index. This
+/// applies both when the derived child secret is not a valid scalar and when
+/// adding it to the parent secret results in zero.
+fn next_valid_child<T>(
+    mut component: Component,
+    mut derive: impl FnMut(Component) -> Result<Option<T>>,
+) -> Result<T> {
+    loop {
+        if let Some(child) = derive(component)? {
+            return Ok(child);
+        }
+        component = match component {
+            Component::Hardened(value) if value < HARDENED - 1 => Component::Hardened(value + 1),
+            Component::Normal(value) if value < HARDENED - 1 => Component::Normal(value + 1),
+            _ => bail!("no valid child key at or after index {component}"),
+        };
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ganache::DETERMINISTIC_MNEMONIC, mnemonic::Mnemonic};
+    use crate::{
+        ganache::{DETERMINISTIC_MNEMONIC, DETERMINISTIC_PRIVATE_KEY},
+        mnemonic::Mnemonic,
+    };
     use ethaddr::address;
 
     #[test]
@@ -73,4 +146,107 @@ mod tests {
             address!("0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1"),
         );
     }
+
+    #[test]
+    fn derive_index_matches_path() {
+        let seed = DETERMINISTIC_MNEMONIC.parse::<Mnemonic>().unwrap().seed("");
+        let path = "m/44'/60'/0'/0/1".parse::<Path>().unwrap();
+        assert_eq!(
+            derive_index(&seed, 1).unwrap().address(),
+            derive(&seed, &path).unwrap().address(),
+        );
+    }
+
+    #[test]
+    fn derive_index_errors_include_full_path() {
+        let seed = DETERMINISTIC_MNEMONIC.parse::<Mnemonic>().unwrap().seed("");
+        let err = derive_index(seed, 1 << 31).unwrap_err();
+        assert!(format!("{err:#}").contains("m/44'/60'/0'/0/2147483648"));
+    }
+
+    #[test]
+    fn cached_derivation_matches_path() {
+        let seed = DETERMINISTIC_MNEMONIC.parse::<Mnemonic>().unwrap().seed("");
+        let derivation = Derivation::new(&seed).unwrap();
+        for index in [0, 1, 42, HARDENED - 1] {
+            assert_eq!(
+                derivation.child(index).unwrap().address(),
+                derive_index(&seed, index as _).unwrap().address(),
+            );
+        }
+
+        let err = derivation.child(HARDENED).unwrap_err();
+        assert!(format!("{err:#}").contains("m/44'/60'/0'/0/2147483648"));
+    }
+
+    #[test]
+    fn derive_range_matches_path() {
+        let seed = DETERMINISTIC_MNEMONIC.parse::<Mnemonic>().unwrap().seed("");
+        let base_path = "m/44'/60'/0'/0".parse::<Path>().unwrap();
+        let addresses = derive_range(&seed, &base_path, 3..6)
+            .unwrap()
+            .map(|key| key.unwrap().address())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            addresses,
+            (3..6)
+                .map(|index| derive_index(&seed, index).unwrap().address())
+                .collect::<Vec<_>>(),
+        );
+
+        let mut keys = derive_range(&seed, &base_path, HARDENED - 1..HARDENED + 1).unwrap();
+        assert!(keys.next().unwrap().is_ok());
+        assert!(keys.next().unwrap().is_err());
+        assert!(keys.next().is_none());
+    }
+
+    #[test]
+    fn cached_bip44_derivation_matches_path() {
+        let seed = DETERMINISTIC_MNEMONIC.parse::<Mnemonic>().unwrap().seed("");
+        let derivation = Derivation::bip44(&seed, 60, 1, 2).unwrap();
+        assert_eq!(
+            derivation.child(3).unwrap().address(),
+            derive(&seed, &Path::bip44(60, 1, 2, 3).unwrap())
+                .unwrap()
+                .address(),
+        );
+
+        let err = derivation.child(HARDENED).unwrap_err();
+        assert!(format!("{err:#}").contains("m/44'/60'/1'/2/2147483648"));
+    }
+
+    #[test]
+    fn skips_invalid_child_keys() {
+        let mut attempts = Vec::new();
+        let child = next_valid_child(Component::Hardened(41), |component| {
+            attempts.push(component);
+            Ok((attempts.len() == 3).then_some(component))
+        })
+        .unwrap();
+
+        assert_eq!(
+            attempts,
+            [
+                Component::Hardened(41),
+                Component::Hardened(42),
+                Component::Hardened(43),
+            ],
+        );
+        assert_eq!(child, Component::Hardened(43));
+    }
+
+    #[test]
+    fn rejects_zero_child_secret() {
+        let parent = SecretKey::from_slice(&DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let child = SecretKey::new(-*parent.as_scalar_primitive());
+        assert!(add_secrets(&child, &parent).is_none());
+
+        let child = SecretKey::from_slice(&[1; 32]).unwrap();
+        assert!(add_secrets(&child, &parent).is_some());
+    }
+
+    #[test]
+    fn errors_when_running_out_of_child_indices() {
+        assert!(next_valid_child(Component::Normal(HARDENED - 2), |_| Ok(None::<()>)).is_err());
+    }
 }
@@ -0,0 +1,420 @@
+//! Module implementing BIP-0032 extended keys and their Base58Check
+//! serialization.
+
+use super::{add_secrets, next_valid_child, Component, Path, HARDENED};
+use crate::{
+    account::{PrivateKey, PublicKey},
+    hash,
+};
+use anyhow::{bail, ensure, Context as _, Result};
+use hmac::{Hmac, Mac as _};
+use k256::{elliptic_curve::sec1::ToEncodedPoint as _, SecretKey};
+use ripemd::{Digest as _, Ripemd160};
+use sha2::Sha512;
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+/// Version bytes for serialized mainnet extended private keys (`xprv`).
+const XPRV: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+/// Version bytes for serialized mainnet extended public keys (`xpub`).
+const XPUB: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+/// The length of a serialized extended key, excluding the checksum.
+const SERIALIZED_LEN: usize = 78;
+
+/// A BIP-0032 extended key. This is either a private or a public key along
+/// with the chain code and metadata required for deriving child keys.
+#[derive(Clone)]
+pub struct ExtendedKey {
+    key: Key,
+    chain_code: [u8; 32],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+}
+
+/// The key of an extended key.
+#[derive(Clone)]
+enum Key {
+    Private(SecretKey),
+    Public(k256::PublicKey),
+}
+
+impl ExtendedKey {
+    /// Creates the master extended private key from a seed.
+    pub fn master(seed: impl AsRef<[u8]>) -> Result<Self> {
+        let mut hmac = Hmac::<Sha512>::new_from_slice(b"Bitcoin seed")?;
+        hmac.update(seed.as_ref());
+        let master_key = hmac.finalize().into_bytes();
+
+        let (secret, chain_code) = master_key.split_at(32);
+        Ok(Self {
+            key: Key::Private(SecretKey::from_slice(secret)?),
+            chain_code: chain_code.try_into()?,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+        })
+    }
+
+    /// Derives the extended key for the specified path relative to this key.
+    pub fn derive(&self, path: &Path) -> Result<Self> {
+        let mut key = self.clone();
+        for (i, component) in path.components().enumerate() {
+            key = key.derive_child(component).with_context(|| {
+                format!("path '{path}' component #{i} yields no valid child key")
+            })?;
+        }
+        Ok(key)
+    }
+
+    /// Derives the child extended key for the specified path component.
+    ///
+    /// If the specified component yields an invalid child key, then the next
+    /// valid child key is returned instead.
+    pub fn derive_child(&self, component: Component) -> Result<Self> {
+        next_valid_child(component, |component| self.child(component))
+    }
+
+    /// Derives the non-hardened child extended public key for the specified
+    /// path component. This does not require the private key, and can be used
+    /// to derive addresses from an extended public key for watch-only wallets.
+    ///
+    /// Returns an error for hardened path components, as these can only be
+    /// derived from extended private keys.
+    pub fn derive_public(&self, component: Component) -> Result<Self> {
+        next_valid_child(component, |component| self.public_child(component))
+    }
+
+    /// Returns the private key, or an error if this is an extended public key.
+    pub fn private_key(&self) -> Result<PrivateKey> {
+        match &self.key {
+            Key::Private(secret) => PrivateKey::new(secret.to_bytes()),
+            Key::Public(_) => bail!("extended public key has no private key"),
+        }
+    }
+
+    /// Returns the public key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(self.k256_public_key())
+    }
+
+    /// Returns the extended public key corresponding to this extended key.
+    pub fn to_public(&self) -> Self {
+        Self {
+            key: Key::Public(self.k256_public_key()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns `true` if this is an extended private key.
+    pub fn is_private(&self) -> bool {
+        matches!(self.key, Key::Private(_))
+    }
+
+    /// Returns the 32-byte chain code.
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    /// Returns the depth of the key, where 0 is the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the fingerprint of the parent key.
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Returns the child number of this key, where hardened children have the
+    /// most significant bit set.
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// Returns the key's fingerprint. This is the first 4 bytes of the
+    /// RIPEMD-160 hash of the SHA-256 hash of the compressed public key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let hash = Ripemd160::digest(hash::sha256(self.encoded_public_key()));
+        hash[..4].try_into().unwrap()
+    }
+
+    /// Computes the child extended key for the specified path component.
+    /// Returns `None` if the resulting child key is invalid.
+    fn child(&self, component: Component) -> Result<Option<Self>> {
+        let Key::Private(secret) = &self.key else {
+            return self.public_child(component);
+        };
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(&self.chain_code)?;
+        let child_number = match component {
+            Component::Hardened(value) => {
+                hmac.update(&[0]);
+                hmac.update(&secret.to_bytes());
+                value | HARDENED
+            }
+            Component::Normal(value) => {
+                hmac.update(&self.encoded_public_key());
+                value
+            }
+        };
+        hmac.update(&child_number.to_be_bytes());
+
+        let child_key = hmac.finalize().into_bytes();
+        let (child_secret, chain_code) = child_key.split_at(32);
+
+        let Some(next_secret) = SecretKey::from_slice(child_secret)
+            .ok()
+            .and_then(|child_secret| add_secrets(&child_secret, secret))
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            key: Key::Private(next_secret),
+            chain_code: chain_code.try_into()?,
+            depth: self
+                .depth
+                .checked_add(1)
+                .context("extended key exceeds maximum depth")?,
+            parent_fingerprint: self.fingerprint(),
+            child_number,
+        }))
+    }
+
+    /// Computes the child extended public key for the specified path
+    /// component using elliptic curve point addition. Returns `None` if the
+    /// resulting child key is invalid.
+    fn public_child(&self, component: Component) -> Result<Option<Self>> {
+        let Component::Normal(child_number) = component else {
+            bail!("can not derive hardened child {component} from an extended public key");
+        };
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(&self.chain_code)?;
+        hmac.update(&self.encoded_public_key());
+        hmac.update(&child_number.to_be_bytes());
+
+        let child_key = hmac.finalize().into_bytes();
+        let (tweak, chain_code) = child_key.split_at(32);
+
+        let parent = self.k256_public_key().to_projective();
+        let Some(next_public) = SecretKey::from_slice(tweak).ok().and_then(|tweak| {
+            let point = tweak.public_key().to_projective() + parent;
+            k256::PublicKey::from_affine(point.to_affine()).ok()
+        }) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            key: Key::Public(next_public),
+            chain_code: chain_code.try_into()?,
+            depth: self
+                .depth
+                .checked_add(1)
+                .context("extended key exceeds maximum depth")?,
+            parent_fingerprint: self.fingerprint(),
+            child_number,
+        }))
+    }
+
+    fn k256_public_key(&self) -> k256::PublicKey {
+        match &self.key {
+            Key::Private(secret) => secret.public_key(),
+            Key::Public(public) => *public,
+        }
+    }
+
+    fn encoded_public_key(&self) -> [u8; 33] {
+        self.k256_public_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .expect("unexpected compressed public key length")
+    }
+}
+
+impl Debug for ExtendedKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedKey")
+            .field("address", &self.public_key().address())
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .finish()
+    }
+}
+
+impl Display for ExtendedKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut buf = [0; SERIALIZED_LEN];
+        buf[4] = self.depth;
+        buf[5..9].copy_from_slice(&self.parent_fingerprint);
+        buf[9..13].copy_from_slice(&self.child_number.to_be_bytes());
+        buf[13..45].copy_from_slice(&self.chain_code);
+        match &self.key {
+            Key::Private(secret) => {
+                buf[0..4].copy_from_slice(&XPRV);
+                buf[46..78].copy_from_slice(&secret.to_bytes());
+            }
+            Key::Public(_) => {
+                buf[0..4].copy_from_slice(&XPUB);
+                buf[45..78].copy_from_slice(&self.encoded_public_key());
+            }
+        }
+
+        f.write_str(&bs58::encode(buf).with_check().into_string())
+    }
+}
+
+impl FromStr for ExtendedKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let buf = bs58::decode(s)
+            .with_check(None)
+            .into_vec()
+            .context("invalid Base58Check encoded extended key")?;
+        ensure!(
+            buf.len() == SERIALIZED_LEN,
+            "invalid extended key length {}",
+            buf.len(),
+        );
+
+        let version: [u8; 4] = buf[0..4].try_into().unwrap();
+        let key = match version {
+            XPRV => {
+                ensure!(buf[45] == 0, "invalid extended private key prefix");
+                Key::Private(SecretKey::from_slice(&buf[46..78])?)
+            }
+            XPUB => Key::Public(k256::PublicKey::from_sec1_bytes(&buf[45..78])?),
+            _ => bail!(
+                "unsupported extended key version 0x{}",
+                hex::encode(version)
+            ),
+        };
+
+        let depth = buf[4];
+        let parent_fingerprint: [u8; 4] = buf[5..9].try_into().unwrap();
+        let child_number = u32::from_be_bytes(buf[9..13].try_into().unwrap());
+        ensure!(
+            depth != 0 || (parent_fingerprint == [0; 4] && child_number == 0),
+            "invalid master extended key with non-zero parent fingerprint or child number",
+        );
+
+        Ok(Self {
+            key,
+            chain_code: buf[13..45].try_into().unwrap(),
+            depth,
+            parent_fingerprint,
+            child_number,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn bip32_test_vector() {
+        let master = ExtendedKey::master(hex!("000102030405060708090a0b0c0d0e0f")).unwrap();
+        for (path, xprv, xpub) in [
+            (
+                "m/0'",
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+                "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+            ),
+            (
+                "m/0'/1",
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+                "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+            ),
+            (
+                "m/0'/1/2'",
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+                "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+            ),
+        ] {
+            let key = master.derive(&path.parse().unwrap()).unwrap();
+            assert_eq!(key.to_string(), xprv);
+            assert_eq!(key.to_public().to_string(), xpub);
+        }
+
+        assert_eq!(
+            master.to_string(),
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+        );
+        assert_eq!(
+            master.to_public().to_string(),
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+        );
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        for s in [
+            "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+        ] {
+            let key = s.parse::<ExtendedKey>().unwrap();
+            assert_eq!(key.to_string(), s);
+            assert_eq!(key.depth(), 2);
+            assert_eq!(key.child_number(), 1);
+        }
+
+        let xpub = "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ"
+            .parse::<ExtendedKey>()
+            .unwrap();
+        assert!(!xpub.is_private());
+        assert!(xpub.private_key().is_err());
+    }
+
+    #[test]
+    fn public_child_derivation() {
+        let xprv = "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"
+            .parse::<ExtendedKey>()
+            .unwrap();
+        let xpub = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw"
+            .parse::<ExtendedKey>()
+            .unwrap();
+
+        let expected = "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ";
+        for key in [&xprv, &xpub] {
+            let child = key.derive_public(Component::Normal(1)).unwrap();
+            assert!(!child.is_private());
+            assert_eq!(child.to_string(), expected);
+        }
+        assert_eq!(
+            xpub.derive(&"m/1".parse().unwrap()).unwrap().to_string(),
+            expected,
+        );
+        assert_eq!(
+            xprv.derive_child(Component::Normal(1))
+                .unwrap()
+                .public_key()
+                .address(),
+            xpub.derive_public(Component::Normal(1))
+                .unwrap()
+                .public_key()
+                .address(),
+        );
+
+        assert!(xprv.derive_public(Component::Hardened(1)).is_err());
+        assert!(xpub.derive_public(Component::Hardened(1)).is_err());
+        assert!(xpub.derive_child(Component::Hardened(1)).is_err());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in [
+            "",
+            "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwR",
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrM",
+        ] {
+            assert!(s.parse::<ExtendedKey>().is_err());
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Module implementing parsing for BIP-0032 HD paths used for key derivation.
 
-use anyhow::{Context as _, Result};
+use super::HARDENED;
+use anyhow::{ensure, Context as _, Result};
 use std::{
     fmt::{self, Display, Formatter},
     str::FromStr,
@@ -14,8 +15,19 @@ pub struct Path {
 
 impl Path {
     /// Creates the default Ethereum HD path for the specified account index.
-    pub fn for_index(index: usize) -> Self {
-        format!("m/44'/60'/0'/0/{index}").parse().unwrap()
+    ///
+    /// Returns an error if the index is out of range for a BIP-0032 path
+    /// component.
+    pub fn for_index(index: usize) -> Result<Self> {
+        Self::bip44(60, 0, 0, index)
+    }
+
+    /// Creates the BIP-0044 HD path `m/44'/{coin_type}'/{account}'/{change}/{index}`.
+    ///
+    /// Returns an error if any of the levels are out of range for a BIP-0032
+    /// path component.
+    pub fn bip44(coin_type: u32, account: u32, change: u32, index: usize) -> Result<Self> {
+        format!("m/44'/{coin_type}'/{account}'/{change}/{index}").parse()
     }
 
     /// Returns an iterator over the path components.
@@ -44,14 +56,15 @@ impl FromStr for Path {
             .context("BIP-0032 path missing main node")?
             .split('/')
             .map(Component::from_str)
-            .collect::<Result<_>>()?;
+            .collect::<Result<_>>()
+            .with_context(|| format!("invalid BIP-0032 path '{s}'"))?;
 
         Ok(Self { components })
     }
 }
 
 /// A hierarchical path component.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Component {
     /// Component to generate a hardened child key.
     Hardened(u32),
@@ -78,8 +91,13 @@ impl FromStr for Component {
         };
 
         let value = value
-            .parse()
+            .parse::<u32>()
             .with_context(|| format!("invalid BIP-0032 path component '{s}'"))?;
+        ensure!(
+            value < HARDENED,
+            "BIP-0032 path component '{s}' out of range, indices must be less \
+             than 2147483648 before hardening",
+        );
 
         Ok(if hardened {
             Component::Hardened(value)
@@ -88,3 +106,62 @@ impl FromStr for Component {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_paths() {
+        let path = "m/44'/60'/0'/0/2147483647".parse::<Path>().unwrap();
+        assert_eq!(
+            path.components().collect::<Vec<_>>(),
+            [
+                Component::Hardened(44),
+                Component::Hardened(60),
+                Component::Hardened(0),
+                Component::Normal(0),
+                Component::Normal(2147483647),
+            ],
+        );
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/2147483647");
+
+        for invalid in [
+            "44'/60'/0'/0/0",
+            "m/44'/60'/0'/0/x",
+            "m/44'/60'/0'/0/2147483648",
+            "m/2147483648'",
+        ] {
+            assert!(invalid.parse::<Path>().is_err());
+        }
+    }
+
+    #[test]
+    fn component_range() {
+        assert_eq!(
+            "2147483647'".parse::<Component>().unwrap(),
+            Component::Hardened(2147483647),
+        );
+        assert_eq!(
+            "2147483647".parse::<Component>().unwrap(),
+            Component::Normal(2147483647),
+        );
+        for invalid in ["2147483648'", "2147483648", "4294967295'"] {
+            let err = invalid.parse::<Component>().unwrap_err();
+            assert!(err.to_string().contains("out of range"));
+        }
+    }
+
+    #[test]
+    fn bip44_paths() {
+        assert_eq!(
+            Path::bip44(60, 1, 2, 3).unwrap().to_string(),
+            "m/44'/60'/1'/2/3",
+        );
+        assert_eq!(
+            Path::bip44(60, 0, 0, 42).unwrap().to_string(),
+            Path::for_index(42).unwrap().to_string(),
+        );
+        assert!(Path::bip44(60, 1 << 31, 0, 0).is_err());
+    }
+}
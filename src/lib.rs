@@ -2,15 +2,19 @@
 //!
 //! This crate implements generating and parsing BIP-0039 mnemonic phrases, as
 //! well as BIP-0032 private key derivation, and various utilities for using
-//! these derived keys for signing various messages relative to Ethereum.
+//! these derived keys for signing various messages relative to Ethereum. It
+//! also supports BIP-0085 deterministic derivation of child mnemonics, and the
+//! SLIP-0039 checksum for Shamir secret-sharing mnemonic shares.
 
 pub mod account;
+pub mod bip85;
 pub mod hash;
 pub mod hdk;
 pub mod message;
 pub mod mnemonic;
 mod rand;
 mod serialization;
+pub mod shamir;
 pub mod transaction;
 pub mod typeddata;
 
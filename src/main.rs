@@ -1,38 +1,97 @@
 mod cmd;
 
-use clap::Parser;
-use std::process;
+use self::cmd::OutputFormat;
+use anyhow::ensure;
+use clap::{Parser, Subcommand};
+use std::{env, process};
 
-#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Parser)]
-#[clap(version, about)]
-enum Options {
+#[clap(version, about, after_help = cmd::CONFIG_HELP)]
+struct Options {
+    /// Print results as JSON objects, one per line, instead of plain text.
+    /// This is supported by the "address", "export", "hash", "public-key",
+    /// "recover" and "sign" subcommands.
+    #[clap(long, global = true)]
+    json: bool,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Subcommand)]
+enum Command {
     #[clap(about = "Print account public address")]
     Address(cmd::address::Options),
+    #[clap(about = "Derive watch-only addresses from an extended public key")]
+    Addresses(cmd::addresses::Options),
     #[clap(about = "Export a private key")]
     Export(cmd::export::Options),
     #[clap(about = "Keccak256 hash data")]
     Hash(cmd::hash::Options),
     #[clap(about = "Hex encoding and decoding")]
     Hex(cmd::hex::Options),
+    #[clap(about = "Decrypt an account from a JSON keystore")]
+    ImportKeystore(cmd::import_keystore::Options),
+    #[clap(about = "BIP-0039 mnemonic phrase utilities")]
+    Mnemonic(cmd::mnemonic::Options),
     #[clap(about = "Generate a new HD wallet mnemonic")]
     New(cmd::new::Options),
     #[clap(about = "Export the public key for an account")]
     PublicKey(cmd::public_key::Options),
+    #[clap(about = "Recover the signer of a signature")]
+    Recover(cmd::recover::Options),
     #[clap(about = "Sign a message")]
     Sign(cmd::sign::Options),
+    #[clap(about = "Inspect an Ethereum transaction")]
+    Transaction(cmd::transaction::Options),
+    #[clap(about = "Verify a signature")]
+    Verify(cmd::verify::Options),
+}
+
+impl Command {
+    /// Returns whether or not the subcommand supports JSON output.
+    fn supports_json(&self) -> bool {
+        matches!(
+            self,
+            Command::Address(_)
+                | Command::Export(_)
+                | Command::Hash(_)
+                | Command::PublicKey(_)
+                | Command::Recover(_)
+                | Command::Sign(_)
+        )
+    }
 }
 
 fn main() {
-    if let Err(err) = match Options::parse() {
-        Options::Address(options) => cmd::address::run(options),
-        Options::Export(options) => cmd::export::run(options),
-        Options::Hash(options) => cmd::hash::run(options),
-        Options::Hex(options) => cmd::hex::run(options),
-        Options::New(options) => cmd::new::run(options),
-        Options::Sign(options) => cmd::sign::run(options),
-        Options::PublicKey(options) => cmd::public_key::run(options),
-    } {
+    let result = cmd::parse_options::<Options>(env::args_os()).and_then(|options| {
+        let output = if options.json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Text
+        };
+        ensure!(
+            output == OutputFormat::Text || options.command.supports_json(),
+            "the '--json' option is not supported by this subcommand",
+        );
+        match options.command {
+            Command::Address(options) => cmd::address::run(options, output),
+            Command::Addresses(options) => cmd::addresses::run(options),
+            Command::Export(options) => cmd::export::run(options, output),
+            Command::Hash(options) => cmd::hash::run(options, output),
+            Command::Hex(options) => cmd::hex::run(options),
+            Command::ImportKeystore(options) => cmd::import_keystore::run(options),
+            Command::Mnemonic(options) => cmd::mnemonic::run(options),
+            Command::New(options) => cmd::new::run(options),
+            Command::Recover(options) => cmd::recover::run(options, output),
+            Command::Sign(options) => cmd::sign::run(options, output),
+            Command::PublicKey(options) => cmd::public_key::run(options, output),
+            Command::Transaction(options) => cmd::transaction::run(options),
+            Command::Verify(options) => cmd::verify::run(options),
+        }
+    });
+    if let Err(err) = result {
         if cfg!(debug_assertions) {
             eprintln!("ERROR: {err:?}");
         } else {
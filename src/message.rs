@@ -1,6 +1,11 @@
 //! Ethereum message for signing.
 
-use crate::hash;
+use crate::{
+    account::Signature,
+    hash::{self, Hash32, Keccak256Hasher},
+};
+use anyhow::Result;
+use ethaddr::Address;
 use std::io::Write as _;
 
 /// A message to be signed with an Ethereum specific prefix.
@@ -11,28 +16,59 @@ where
     T: AsRef<[u8]>,
 {
     /// Computes the 32-byte message used for ECDSA signing with a private key.
-    pub fn signing_message(&self) -> [u8; 32] {
+    pub fn signing_message(&self) -> Hash32 {
         digest(self.0.as_ref())
     }
 }
 
+/// An EIP-191 version `0x00` message, that is data with an intended validator.
+///
+/// The signing message is computed as `keccak256(0x19 || 0x00 || validator ||
+/// data)`, where the validator is typically the address of the contract that
+/// verifies the signature.
+pub struct ValidatorMessage {
+    /// The address of the intended validator.
+    pub validator: Address,
+    /// The data to sign.
+    pub data: Vec<u8>,
+}
+
+impl ValidatorMessage {
+    /// Computes the 32-byte message used for ECDSA signing with a private key.
+    pub fn signing_message(&self) -> Hash32 {
+        hash::keccak256_parts(&[b"\x19\x00", &*self.validator, &self.data])
+    }
+}
+
+/// Verifies a `personal_sign` (or equivalently `eth_sign`) signature over the
+/// specified data, returning the address of the signer.
+///
+/// Note that this only recovers the signer; callers must still check that the
+/// returned address is the expected one.
+pub fn verify_personal_sign(data: &[u8], signature: &Signature) -> Result<Address> {
+    signature.recover_address(EthereumMessage(data).signing_message())
+}
+
 /// Computes the Ethereum-specific digest for a message.
-fn digest(data: &[u8]) -> [u8; 32] {
-    let mut buffer = Vec::with_capacity(46 + data.len());
-    buffer.extend_from_slice(b"\x19Ethereum Signed Message:\n");
+fn digest(data: &[u8]) -> Hash32 {
+    let mut hasher = Keccak256Hasher::new();
+    hasher.update(b"\x19Ethereum Signed Message:\n");
     // Display implementation for `usize` should not error when writing to an
-    // in memory buffer. Note that the standard library `ToString::to_string`
+    // in memory hasher. Note that the standard library `ToString::to_string`
     // implementation has the same expectation:
     // <https://doc.rust-lang.org/std/string/trait.ToString.html#required-methods>
-    write!(buffer, "{}", data.len()).expect("unexpected error writing number");
-    buffer.extend_from_slice(data);
+    write!(hasher, "{}", data.len()).expect("unexpected error writing number");
+    hasher.update(data);
 
-    hash::keccak256(buffer)
+    Hash32(hasher.finalize())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{account::PrivateKey, ganache::DETERMINISTIC_PRIVATE_KEY};
+    use ethaddr::address;
+    use hex_literal::hex;
 
     #[test]
     fn computes_digest() {
@@ -41,4 +77,37 @@ mod tests {
             hash::keccak256(b"\x19Ethereum Signed Message:\n12hello world!"),
         );
     }
+
+    #[test]
+    fn computes_validator_digest() {
+        let message = ValidatorMessage {
+            validator: address!("0x1111111111111111111111111111111111111111"),
+            data: b"Hello World!".to_vec(),
+        };
+        assert_eq!(
+            message.signing_message(),
+            hex!("c1bbf904da8ae156efd55334bb52f4a7e0e218566db96446d2f3dee1972dad02"),
+        );
+    }
+
+    #[test]
+    fn verifies_personal_sign() {
+        let signature = "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84\
+                           61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859\
+                           1c"
+        .parse::<Signature>()
+        .unwrap();
+        let signer = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY)
+            .unwrap()
+            .address();
+
+        assert_eq!(
+            verify_personal_sign(b"Hello World!", &signature).unwrap(),
+            signer,
+        );
+        assert_ne!(
+            verify_personal_sign(b"Hello World?", &signature).unwrap(),
+            signer,
+        );
+    }
 }
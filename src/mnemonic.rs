@@ -5,13 +5,14 @@ mod wordlist;
 
 pub use self::{language::Language, wordlist::WORD_COUNT};
 use crate::{hash, rand};
-use anyhow::{ensure, Context as _, Result};
+use anyhow::{anyhow, bail, ensure, Context as _, Result};
 use hmac::Hmac;
 use sha2::Sha512;
 use std::{
     convert::TryInto,
+    error::Error,
     fmt::{self, Display, Formatter},
-    mem,
+    io, mem,
     ops::Deref,
     str::FromStr,
 };
@@ -39,17 +40,55 @@ impl Mnemonic {
     /// word length.
     ///
     /// This method returns an error if it fails if the specified mnemonic word
-    /// length is invalid (it must be in the range `12..=24`) or if there is an
-    /// error reading cryptographically strong entropy from the operating
+    /// length is invalid (it must be one of 12, 15, 18, 21 or 24) or if there
+    /// is an error reading cryptographically strong entropy from the operating
     /// system.
     pub fn random(language: Language, mnemonic_length: usize) -> Result<Self> {
+        Self::random_with(language, mnemonic_length, |seed| rand::get_entropy(seed))
+    }
+
+    /// Generates a new seed for the specified mnemonic word length, using the
+    /// provided function to fill the seed bytes with entropy.
+    ///
+    /// This allows injecting a deterministic entropy source for tests and
+    /// reproducible generation. Use [`Mnemonic::random`] for generating real
+    /// wallets.
+    pub fn random_with(
+        language: Language,
+        mnemonic_length: usize,
+        fill: impl FnOnce(&mut [u8]) -> io::Result<()>,
+    ) -> Result<Self> {
         let len = mnemonic_to_byte_length(mnemonic_length)?;
         let buf = {
             let mut buf = [0; 64];
             let (seed, hash) = buf.split_at_mut(len);
 
-            rand::get_entropy(&mut *seed)?;
-            hash[..32].copy_from_slice(&hash::sha256(seed));
+            fill(seed)?;
+            hash[..32].copy_from_slice(&*hash::sha256(seed));
+
+            buf
+        };
+
+        Ok(Self { language, buf, len })
+    }
+
+    /// Creates a mnemonic from raw entropy bytes.
+    ///
+    /// This method returns an error if the entropy has an invalid length for
+    /// BIP-0039 mnemonics (it must be 16, 20, 24, 28 or 32 bytes long).
+    pub fn from_entropy(language: Language, entropy: &[u8]) -> Result<Self> {
+        let len = entropy.len();
+        ensure!(
+            matches!(len, 16 | 20 | 24 | 28 | 32),
+            "invalid entropy length {len}, must be 16, 20, 24, 28 or 32 bytes",
+        );
+
+        let buf = {
+            let mut buf = [0; 64];
+            let (seed, hash) = buf.split_at_mut(len);
+
+            seed.copy_from_slice(entropy);
+            hash[..32].copy_from_slice(&*hash::sha256(seed));
 
             buf
         };
@@ -64,21 +103,106 @@ impl Mnemonic {
 
     fn from_phrase_str(mnemonic: &str) -> Result<Self> {
         let (language, words) = Language::split(mnemonic)?;
+        Self::from_words(language, &words).map_err(|err| match err {
+            MnemonicError::UnknownWord { word, .. } => anyhow!(invalid_word(language, &word)),
+            err => err.into(),
+        })
+    }
 
-        let len = mnemonic_to_byte_length(words.len())?;
-        let buf = {
-            let wordlist = language.wordlist();
+    /// Validates a mnemonic phrase, returning a detailed error describing why
+    /// the phrase is not a valid BIP-0039 mnemonic. This allows distinguishing
+    /// between misspelled words and checksum failures.
+    pub fn validate(phrase: &str) -> Result<(), MnemonicError> {
+        let (language, words) = Language::split(phrase)?;
+        Self::from_words(language, &words)?;
+        Ok(())
+    }
+
+    /// Creates a mnemonic from its words, verifying the checksum.
+    fn from_words(language: Language, words: &[&str]) -> Result<Self, MnemonicError> {
+        let wordlist = language.wordlist();
+        let indices = words
+            .iter()
+            .enumerate()
+            .map(|(index, &word)| {
+                wordlist
+                    .search(word)
+                    .ok_or_else(|| MnemonicError::UnknownWord {
+                        index,
+                        word: word.to_owned(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_indices(language, &indices)
+    }
+
+    /// Computes all valid final words for an incomplete mnemonic phrase, that
+    /// is, the words that, when appended to the partial phrase, produce a
+    /// mnemonic with a valid checksum.
+    ///
+    /// This method returns an error if the partial phrase contains invalid
+    /// words, or if it is not exactly one word short of a valid mnemonic
+    /// length.
+    pub fn complete(language: Language, partial: &[&str]) -> Result<Vec<String>> {
+        mnemonic_to_byte_length(partial.len() + 1).with_context(|| {
+            format!(
+                "partial mnemonic with {} words is not one word short of a valid length",
+                partial.len(),
+            )
+        })?;
+
+        let mut indices = word_indices(language, partial)?;
+        indices.push(0);
+
+        Ok((0..WORD_COUNT)
+            .filter(|&index| {
+                *indices.last_mut().unwrap() = index;
+                Self::from_indices(language, &indices).is_ok()
+            })
+            .map(|index| language.wordlist().word(index).to_owned())
+            .collect())
+    }
+
+    /// Expands a mnemonic phrase where words may be abbreviated to unique
+    /// prefixes (such as the first four letters of each word, which are
+    /// unique for all BIP-0039 word lists) into a full mnemonic.
+    ///
+    /// Words that exactly match a word in the list are always accepted, even
+    /// if they are also the prefix of other words. This method returns an
+    /// error if an abbreviation is ambiguous or does not match any word, or if
+    /// the resulting mnemonic is invalid.
+    pub fn expand(language: Language, abbreviated: &[&str]) -> Result<Self> {
+        let wordlist = language.wordlist();
+        let indices = abbreviated
+            .iter()
+            .map(|&word| {
+                if let Some(index) = wordlist.search(word) {
+                    return Ok(index);
+                }
+                let matches = wordlist.search_prefix(word);
+                match matches.len() {
+                    0 => bail!("invalid BIP-0039 {language} word prefix '{word}'"),
+                    1 => Ok(matches.start),
+                    n => bail!(
+                        "ambiguous BIP-0039 {language} word prefix '{word}' matches {n} words"
+                    ),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_indices(language, &indices)?)
+    }
 
+    /// Creates a mnemonic from its word indices, verifying the checksum.
+    fn from_indices(language: Language, indices: &[usize]) -> Result<Self, MnemonicError> {
+        let len = mnemonic_to_byte_length(indices.len())?;
+        let buf = {
             let mut buf = [0; 64];
             let (seed, hash) = buf.split_at_mut(len);
 
             let mut acc = 0;
             let mut bit_offset = 0;
             let mut byte_offset = 0;
-            for word in &words {
-                let index = wordlist
-                    .search(word)
-                    .with_context(|| format!("invalid BIP-0039 {language} word '{word}'"))?;
+            for &index in indices {
                 acc = (acc << WORD_BITS) | index;
 
                 bit_offset += WORD_BITS;
@@ -91,16 +215,15 @@ impl Mnemonic {
 
             // NOTE: The remaining bits are checksum bits that we need to
             // verify now.
-            debug_assert_eq!(len * 8 + bit_offset, words.len() * WORD_BITS);
+            debug_assert_eq!(len * 8 + bit_offset, indices.len() * WORD_BITS);
             debug_assert_eq!(byte_offset, len);
 
-            hash[..32].copy_from_slice(&hash::sha256(seed));
+            hash[..32].copy_from_slice(&*hash::sha256(seed));
 
             let checksum_mask = (1 << bit_offset) - 1;
-            ensure!(
-                hash[0] >> (8 - bit_offset) == (acc & checksum_mask) as u8,
-                "mnemonic checksum verification failure",
-            );
+            if hash[0] >> (8 - bit_offset) != (acc & checksum_mask) as u8 {
+                return Err(MnemonicError::ChecksumMismatch);
+            }
 
             buf
         };
@@ -108,12 +231,31 @@ impl Mnemonic {
         Ok(Self { language, buf, len })
     }
 
-    /// Gets the mnemonic's binary representation as a slice of bytes.
-    #[cfg(test)]
-    pub fn as_bytes(&self) -> &[u8] {
+    /// Gets the BIP-0039 entropy encoded by the mnemonic.
+    ///
+    /// Note that this is the raw entropy that the mnemonic words encode (with
+    /// the checksum bits removed), and **not** the PBKDF2 derived seed used for
+    /// HD wallets (see [`Mnemonic::seed`]). The entropy can be used with
+    /// [`Mnemonic::from_entropy`] to recreate the mnemonic, for example, in a
+    /// different language.
+    pub fn entropy(&self) -> &[u8] {
         &self.buf[..self.len]
     }
 
+    /// Re-encodes the mnemonic's entropy with the word list of the specified
+    /// language.
+    ///
+    /// Note that the BIP-0039 seed is derived from the mnemonic phrase, and not
+    /// its entropy, so mnemonics in different languages produce different
+    /// seeds. Converting back to the original language restores the original
+    /// phrase and seed.
+    pub fn to_language(&self, language: Language) -> Self {
+        Self {
+            language,
+            ..self.clone()
+        }
+    }
+
     /// Gets the BIP-0039 mnemonic word length.
     pub fn mnemonic_length(&self) -> usize {
         ((self.len * 8) / WORD_BITS) + 1
@@ -195,8 +337,74 @@ impl Deref for Seed {
     }
 }
 
-fn mnemonic_to_byte_length(len: usize) -> Result<usize> {
-    ensure!(matches!(len, 12..=24), "invalid mnemonic length {len}");
+/// An error validating a BIP-0039 mnemonic phrase.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MnemonicError {
+    /// The word at the specified (zero-based) index is not in the word list.
+    UnknownWord { index: usize, word: String },
+    /// The mnemonic has an invalid number of words.
+    InvalidLength(usize),
+    /// All words are valid, but the mnemonic's checksum does not match.
+    ChecksumMismatch,
+    /// None of the words belong to a supported word list.
+    UnknownLanguage,
+    /// All words belong to supported word lists, but not to a single one.
+    MixedLanguages,
+}
+
+impl Display for MnemonicError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MnemonicError::UnknownWord { index, word } => {
+                write!(f, "invalid BIP-0039 word '{word}' at index {index}")
+            }
+            MnemonicError::InvalidLength(len) => {
+                write!(f, "mnemonic must be 12, 15, 18, 21, or 24 words, got {len}")
+            }
+            MnemonicError::ChecksumMismatch => {
+                f.write_str("mnemonic checksum verification failure")
+            }
+            MnemonicError::UnknownLanguage => {
+                f.write_str("mnemonic words do not belong to any supported BIP-0039 word list")
+            }
+            MnemonicError::MixedLanguages => {
+                f.write_str("mnemonic words belong to different BIP-0039 word lists")
+            }
+        }
+    }
+}
+
+impl Error for MnemonicError {}
+
+/// Returns the word list indices for the specified mnemonic words.
+fn word_indices(language: Language, words: &[&str]) -> Result<Vec<usize>> {
+    let wordlist = language.wordlist();
+    words
+        .iter()
+        .map(|word| {
+            wordlist
+                .search(word)
+                .with_context(|| invalid_word(language, word))
+        })
+        .collect()
+}
+
+/// Returns an error message for an invalid mnemonic word, including
+/// suggestions for similar valid words.
+fn invalid_word(language: Language, word: &str) -> String {
+    let mut message = format!("invalid BIP-0039 {language} word '{word}'");
+    let suggestions = language.wordlist().suggest(word);
+    if !suggestions.is_empty() {
+        let suggestions = &suggestions[..suggestions.len().min(3)];
+        message.push_str(&format!(", did you mean: {}", suggestions.join(", ")));
+    }
+    message
+}
+
+fn mnemonic_to_byte_length(len: usize) -> Result<usize, MnemonicError> {
+    if !matches!(len, 12 | 15 | 18 | 21 | 24) {
+        return Err(MnemonicError::InvalidLength(len));
+    }
 
     // NOTE: Derived from the BIP-0039 spec where `CS` is the checksum bit
     // length, `ENT` is the entropy bit length (so `8 * byte_length`) and `MS`
@@ -220,12 +428,214 @@ mod tests {
             &[(128, 12), (160, 15), (192, 18), (224, 21), (256, 24)]
         {
             let mnemonic = Mnemonic::random(Language::English, mnemonic_length).unwrap();
-            assert_eq!(mnemonic.as_bytes().len() * 8, bit_length);
-            let all_zeros = mnemonic.as_bytes().iter().all(|&byte| byte == 0);
+            assert_eq!(mnemonic.entropy().len() * 8, bit_length);
+            let all_zeros = mnemonic.entropy().iter().all(|&byte| byte == 0);
             assert!(!all_zeros);
         }
     }
 
+    #[test]
+    fn random_mnemonic_with_entropy_source() {
+        let mnemonic = Mnemonic::random_with(Language::English, 12, |seed| {
+            seed.fill(0x7f);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            mnemonic.to_phrase(),
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        );
+
+        assert!(Mnemonic::random_with(Language::English, 12, |_| {
+            Err(io::Error::other("no entropy"))
+        })
+        .is_err());
+        assert!(Mnemonic::random_with(Language::English, 13, |_| unreachable!()).is_err());
+    }
+
+    #[test]
+    fn invalid_mnemonic_lengths() {
+        for len in [0, 9, 11, 13, 14, 16, 17, 19, 20, 22, 23, 25, 27] {
+            let err = mnemonic_to_byte_length(len).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("mnemonic must be 12, 15, 18, 21, or 24 words, got {len}"),
+            );
+            assert!(Mnemonic::random(Language::English, len).is_err());
+        }
+
+        for len in [13, 20] {
+            let phrase = vec!["abandon"; len].join(" ");
+            assert_eq!(
+                Mnemonic::validate(&phrase),
+                Err(MnemonicError::InvalidLength(len)),
+            );
+            assert_eq!(
+                Mnemonic::from_phrase(&phrase).unwrap_err().to_string(),
+                format!("mnemonic must be 12, 15, 18, 21, or 24 words, got {len}"),
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_word_suggestions() {
+        let err = Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon abot",
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid BIP-0039 English word 'abot', did you mean: about",
+        );
+
+        let err = Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon xyzzy",
+        )
+        .unwrap_err();
+        assert_eq!(format!("{err:#}"), "invalid BIP-0039 English word 'xyzzy'");
+    }
+
+    #[test]
+    fn validate_mnemonic() {
+        assert_eq!(
+            Mnemonic::validate(
+                "abandon abandon abandon abandon abandon abandon \
+                 abandon abandon abandon abandon abandon about",
+            ),
+            Ok(()),
+        );
+        assert_eq!(
+            Mnemonic::validate(
+                "abandon abandon abandon abandon abandon abndon \
+                 abandon abandon abandon abandon abandon about",
+            ),
+            Err(MnemonicError::UnknownWord {
+                index: 5,
+                word: "abndon".to_owned(),
+            }),
+        );
+        assert_eq!(
+            Mnemonic::validate("xyzzy plugh"),
+            Err(MnemonicError::UnknownLanguage),
+        );
+        assert_eq!(
+            Mnemonic::validate("abandon abandon abandon about"),
+            Err(MnemonicError::InvalidLength(4)),
+        );
+        assert_eq!(
+            Mnemonic::validate(
+                "abandon abandon abandon abandon abandon abandon \
+                 abandon abandon abandon abandon abandon abandon",
+            ),
+            Err(MnemonicError::ChecksumMismatch),
+        );
+    }
+
+    #[test]
+    fn mnemonic_from_entropy() {
+        let mnemonic = Mnemonic::from_entropy(
+            Language::English,
+            &hex!("f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f"),
+        )
+        .unwrap();
+        assert_eq!(
+            mnemonic.to_phrase(),
+            "void come effort suffer camp survey \
+             warrior heavy shoot primary clutch crush \
+             open amazing screen patrol group space \
+             point ten exist slush involve unfold",
+        );
+
+        for len in [0, 15, 17, 33] {
+            assert!(Mnemonic::from_entropy(Language::English, &vec![0; len]).is_err());
+        }
+    }
+
+    #[test]
+    fn mnemonic_entropy_roundtrip() {
+        for len in [16, 20, 24, 28, 32] {
+            for fill in [0x00, 0x7f, 0x80, 0xff] {
+                let entropy = (0..len)
+                    .map(|i| fill ^ (i as u8).wrapping_mul(37))
+                    .collect::<Vec<_>>();
+                let mnemonic = Mnemonic::from_entropy(Language::English, &entropy).unwrap();
+                assert_eq!(mnemonic.entropy(), entropy);
+                assert_eq!(mnemonic.mnemonic_length(), len * 3 / 4);
+
+                let parsed = Mnemonic::from_phrase(mnemonic.to_phrase()).unwrap();
+                assert_eq!(parsed.entropy(), entropy);
+            }
+        }
+    }
+
+    #[test]
+    fn mnemonic_language_roundtrip() {
+        let mnemonic = Mnemonic::from_phrase(
+            "myth like bonus scare over problem client lizard pioneer submit female collect",
+        )
+        .unwrap();
+        for language in Language::ALL {
+            let converted = mnemonic.to_language(language);
+            assert_eq!(converted.entropy(), mnemonic.entropy());
+
+            let roundtrip = converted.to_language(Language::English);
+            assert_eq!(roundtrip.to_phrase(), mnemonic.to_phrase());
+            assert_eq!(*roundtrip.seed("password"), *mnemonic.seed("password"));
+        }
+    }
+
+    #[test]
+    fn complete_mnemonic() {
+        let words = Mnemonic::complete(Language::English, &["abandon"; 11]).unwrap();
+        assert_eq!(words.len(), 128);
+        assert_eq!(
+            words[..8],
+            ["about", "actual", "age", "alpha", "angle", "argue", "artwork", "attract"],
+        );
+
+        let words = Mnemonic::complete(Language::English, &["abandon"; 23]).unwrap();
+        assert_eq!(
+            words,
+            ["art", "diesel", "false", "kite", "organ", "ready", "surface", "trouble"],
+        );
+
+        assert!(Mnemonic::complete(Language::English, &["abandon"; 12]).is_err());
+
+        let mut partial = ["abandon"; 11];
+        partial[4] = "klingon";
+        assert!(Mnemonic::complete(Language::English, &partial).is_err());
+    }
+
+    #[test]
+    fn expand_abbreviated_mnemonic() {
+        let mnemonic = Mnemonic::expand(
+            Language::English,
+            &[
+                "myth", "like", "bonu", "scar", "over", "prob", "clie", "liza", "pion", "subm",
+                "fema", "coll",
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            mnemonic.to_phrase(),
+            "myth like bonus scare over problem client lizard pioneer submit female collect",
+        );
+
+        let mut abbreviated = ["aban"; 12];
+        abbreviated[11] = "abou";
+        let mnemonic = Mnemonic::expand(Language::English, &abbreviated).unwrap();
+        assert_eq!(mnemonic.to_phrase(), ["abandon"; 11].join(" ") + " about");
+
+        abbreviated[11] = "act";
+        assert!(Mnemonic::expand(Language::English, &abbreviated).is_err());
+        abbreviated[11] = "ab";
+        assert!(Mnemonic::expand(Language::English, &abbreviated).is_err());
+        abbreviated[11] = "xyz";
+        assert!(Mnemonic::expand(Language::English, &abbreviated).is_err());
+    }
+
     #[test]
     fn mnemonic_phrases() {
         for &(bytes, phrase, password, seed) in &[
@@ -283,7 +693,7 @@ mod tests {
             ),
         ] {
             let mnemonic = Mnemonic::from_phrase(phrase).unwrap();
-            assert_eq!(mnemonic.as_bytes(), bytes);
+            assert_eq!(mnemonic.entropy(), bytes);
             assert_eq!(*mnemonic.seed(password), seed);
             assert_eq!(mnemonic.to_phrase(), phrase);
         }
@@ -1,6 +1,9 @@
 //! Mnemonic language for selecting word lists.
 
-use crate::mnemonic::wordlist::{self, Wordlist};
+use crate::mnemonic::{
+    wordlist::{self, Wordlist},
+    MnemonicError,
+};
 use anyhow::{bail, Result};
 use std::{
     fmt::{self, Display, Formatter},
@@ -19,18 +22,63 @@ pub enum Language {
 }
 
 impl Language {
+    /// All supported mnemonic languages, in the order that they are considered
+    /// when detecting the language of a mnemonic phrase.
+    pub const ALL: [Self; 1] = [Language::English];
+
     /// Splits a mnemonic phrase into its words, returning the detected language
     /// and a vector of **normalized** words.
-    pub fn split(phrase: &str) -> Result<(Self, Vec<&str>)> {
-        // TODO(nlordell): A lot to do here...
-        let language = Language::English;
-        Ok((
-            language,
-            phrase
-                .split_whitespace()
-                .filter(|word| !word.is_empty())
-                .collect(),
-        ))
+    ///
+    /// The language is detected by testing the words against each language's
+    /// word list. Words that are prefixes of words in a list are considered to
+    /// belong to that list, so that abbreviated phrases are also supported.
+    /// This returns an error if the words belong to different word lists, or
+    /// if none of the words belong to any word list. If some words are not
+    /// part of any word list (for example, because of spelling mistakes), the
+    /// language matching the most words is returned.
+    pub fn split(phrase: &str) -> Result<(Self, Vec<&str>), MnemonicError> {
+        // TODO(nlordell): Normalize words for languages with specific
+        // considerations (like 'ñ' being equivalent to 'n' in Spanish).
+        let words = phrase
+            .split_whitespace()
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+        let language = Self::detect(&words)?;
+        Ok((language, words))
+    }
+
+    /// Detects the language of mnemonic words.
+    fn detect(words: &[&str]) -> Result<Self, MnemonicError> {
+        let matches =
+            |language: Self, word: &str| !language.wordlist().search_prefix(word).is_empty();
+
+        if let Some(language) = Self::ALL
+            .into_iter()
+            .find(|&language| words.iter().all(|word| matches(language, word)))
+        {
+            return Ok(language);
+        }
+        if words.iter().all(|word| {
+            Self::ALL
+                .into_iter()
+                .any(|language| matches(language, word))
+        }) {
+            return Err(MnemonicError::MixedLanguages);
+        }
+
+        // NOTE: `max_by_key` returns the last maximum element, so reverse the
+        // languages in order to prefer earlier languages on ties.
+        Self::ALL
+            .into_iter()
+            .rev()
+            .map(|language| {
+                let count = words.iter().filter(|word| matches(language, word)).count();
+                (language, count)
+            })
+            .max_by_key(|(_, count)| *count)
+            .filter(|(_, count)| *count > 0)
+            .map(|(language, _)| language)
+            .ok_or(MnemonicError::UnknownLanguage)
     }
 
     /// Returns the language's wordlist.
@@ -66,3 +114,32 @@ impl FromStr for Language {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_language() {
+        for phrase in [
+            "",
+            "abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon about",
+            // Abbreviated words.
+            "aban aban aban aban aban aban aban aban aban aban aban abou",
+            // Some unknown words.
+            "abandon abandon abandon abandon abandon abndon \
+             abandon abandon abandon abandon abandon xyzzy",
+        ] {
+            assert!(matches!(
+                Language::split(phrase),
+                Ok((Language::English, _))
+            ));
+        }
+
+        assert_eq!(
+            Language::split("xyzzy plugh").unwrap_err(),
+            MnemonicError::UnknownLanguage,
+        );
+    }
+}
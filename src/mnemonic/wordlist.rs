@@ -1,7 +1,7 @@
 //! Module containing the embedded BIP-0039 word lists.
 
 use crate::mnemonic::Language;
-use std::sync::OnceLock;
+use std::{ops::Range, sync::OnceLock};
 
 /// A parsed word list.
 pub struct Wordlist<'a>(Vec<&'a str>);
@@ -34,6 +34,41 @@ impl<'a> Wordlist<'a> {
         self.0.binary_search(&word.as_ref()).ok()
     }
 
+    /// Searches the word list for all words starting with the specified
+    /// prefix, returning the range of their indices in the list. The range is
+    /// empty if no words match the prefix.
+    pub fn search_prefix(&self, prefix: impl AsRef<str>) -> Range<usize> {
+        let prefix = prefix.as_ref();
+        let start = self.0.partition_point(|word| *word < prefix);
+        let len = self.0[start..]
+            .iter()
+            .take_while(|word| word.starts_with(prefix))
+            .count();
+        start..start + len
+    }
+
+    /// Suggests words from the list that are similar to the specified word,
+    /// for example to help with correcting spelling mistakes. This includes
+    /// all words that start with the specified word and all words that are
+    /// within an edit distance of 1. Suggestions are ordered by their edit
+    /// distance to the specified word, with closer matches first.
+    pub fn suggest(&'a self, word: impl AsRef<str>) -> Vec<&'a str> {
+        let word = word.as_ref();
+        let mut suggestions = self
+            .0
+            .iter()
+            .filter_map(|&candidate| {
+                let distance = levenshtein(word, candidate);
+                (distance <= 1 || candidate.starts_with(word)).then_some((distance, candidate))
+            })
+            .collect::<Vec<_>>();
+        suggestions.sort_by_key(|(distance, _)| *distance);
+        suggestions
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
     /// Returns the word for the specified index.
     ///
     /// # Panics
@@ -46,6 +81,22 @@ impl<'a> Wordlist<'a> {
     }
 }
 
+/// Computes the Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 macro_rules! match_language {
     ($lang:expr; $(
         $l:ident => $f:expr,
@@ -76,4 +127,43 @@ mod tests {
     fn parses_wordlists() {
         for_language(Language::English);
     }
+
+    #[test]
+    fn search_word_prefixes() {
+        let wordlist = for_language(Language::English);
+        assert_eq!(
+            wordlist.search_prefix("aban"),
+            wordlist
+                .search("abandon")
+                .map(|index| index..index + 1)
+                .unwrap(),
+        );
+        assert_eq!(wordlist.search_prefix("zoo"), 2047..2048);
+        assert_eq!(wordlist.search_prefix("act").len(), 5);
+        assert_eq!(wordlist.search_prefix("ab").len(), 10);
+        assert!(wordlist.search_prefix("xyz").is_empty());
+    }
+
+    #[test]
+    fn levenshtein_distance() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("word", "wrd"), 1);
+        assert_eq!(levenshtein("word", "wodr"), 2);
+    }
+
+    #[test]
+    fn suggest_similar_words() {
+        let wordlist = for_language(Language::English);
+        assert_eq!(wordlist.suggest("abandn"), ["abandon"]);
+        assert_eq!(wordlist.suggest("helo"), ["hello", "help", "hero"]);
+        assert_eq!(wordlist.suggest("wrd"), ["word"]);
+        assert_eq!(
+            wordlist.suggest("act"),
+            ["act", "art", "pact", "actor", "action", "actual", "actress"],
+        );
+        assert!(wordlist.suggest("xyzzy").is_empty());
+    }
 }
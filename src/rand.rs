@@ -1,20 +1,72 @@
 //! Cryptographically secure randomness.
 
-use std::{io, os::raw::c_int};
+use std::io;
+
+/// The maximum number of bytes of entropy that can be read at once.
+///
+/// This matches the limit of the `getentropy` system call, so that behaviour
+/// is consistent across platforms.
+const MAX_SIZE: usize = 256;
 
 /// Populates the provided slice with cryptographically strong entropy.
 pub fn get_entropy(mut buf: impl AsMut<[u8]>) -> io::Result<()> {
     let buf = buf.as_mut();
-    let result = unsafe { getentropy(buf.as_mut_ptr(), buf.len()) };
-    if result >= 0 {
-        Ok(())
-    } else {
-        Err(io::Error::last_os_error())
+    if buf.len() > MAX_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot read more than {MAX_SIZE} bytes of entropy"),
+        ));
+    }
+
+    #[cfg(feature = "deterministic-rand")]
+    if deterministic::fill(buf)? {
+        return Ok(());
     }
+
+    getrandom::getrandom(buf)?;
+    Ok(())
 }
 
-extern "C" {
-    fn getentropy(buffer: *mut u8, len: usize) -> c_int;
+/// Deterministic entropy for reproducible tests.
+///
+/// This is **only** intended for testing and must never be used for
+/// generating real wallets, as the entropy is entirely predictable from the
+/// seed. In order to prevent accidentally shipping it, the feature can not be
+/// enabled for release builds.
+#[cfg(feature = "deterministic-rand")]
+mod deterministic {
+    use crate::hash;
+    use std::{
+        env, io,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    #[cfg(not(debug_assertions))]
+    compile_error!("the `deterministic-rand` feature must not be used for release builds");
+
+    /// The environment variable used for seeding the deterministic entropy.
+    const SEED_VAR: &str = "HDWALLET_TEST_SEED";
+
+    /// Block counter, so that successive calls produce different entropy.
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    /// Fills the buffer with deterministic entropy derived from the seed in
+    /// the environment, computing each 32-byte block as
+    /// `sha256(seed || counter)`. Returns `false` if no seed is set.
+    pub fn fill(buf: &mut [u8]) -> io::Result<bool> {
+        let seed = match env::var(SEED_VAR) {
+            Ok(seed) => seed,
+            Err(env::VarError::NotPresent) => return Ok(false),
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidInput, err)),
+        };
+
+        for chunk in buf.chunks_mut(32) {
+            let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let block = hash::sha256([seed.as_bytes(), &counter.to_be_bytes()].concat());
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        Ok(true)
+    }
 }
 
 #[cfg(test)]
@@ -28,6 +80,15 @@ mod tests {
         assert!(get_entropy(&mut buf).is_ok());
     }
 
+    #[test]
+    fn read_distinct_os_entropy() {
+        let (mut a, mut b) = ([0u8; MAX_SIZE], [0u8; MAX_SIZE]);
+        get_entropy(&mut a).unwrap();
+        get_entropy(&mut b).unwrap();
+        assert_ne!(a, [0; MAX_SIZE]);
+        assert_ne!(a, b);
+    }
+
     #[test]
     fn error_reading_more_than_256_bytes() {
         let mut buf = [0u8; 257];
@@ -44,6 +44,24 @@ pub mod bytes {
             .ok_or_else(|| de::Error::custom("storage slot missing '0x' prefix"))?;
         hex::decode(s).map_err(de::Error::custom)
     }
+
+    /// Dynamic byte array deserialization with an optional `0x` prefix.
+    pub mod permissive {
+        use serde::{
+            de::{self, Deserializer},
+            Deserialize as _,
+        };
+        use std::borrow::Cow;
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s = Cow::<str>::deserialize(deserializer)?;
+            let s = s.strip_prefix("0x").unwrap_or(&s);
+            hex::decode(s).map_err(de::Error::custom)
+        }
+    }
 }
 
 /// Fixed byte array serialization methods.
@@ -0,0 +1,5 @@
+//! Module implementing SLIP-0039 Shamir's secret-sharing for mnemonic codes.
+//!
+//! <https://github.com/satoshilabs/slips/blob/master/slip-0039.md>
+
+pub mod rs1024;
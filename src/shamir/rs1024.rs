@@ -0,0 +1,87 @@
+//! Module implementing the RS1024 checksum used by SLIP-0039 share mnemonics.
+//!
+//! This is a Reed-Solomon code over GF(1024) that guarantees detection of any
+//! error affecting at most 3 words, computed over the 10-bit word indices of a
+//! share mnemonic.
+
+/// The customization string for non-extendable SLIP-0039 shares.
+const CUSTOMIZATION_STRING: &[u8] = b"shamir";
+
+/// The number of 10-bit checksum words.
+pub const CHECKSUM_LEN: usize = 3;
+
+/// Computes the RS1024 checksum words for the specified 10-bit values.
+pub fn create_checksum(values: &[u16]) -> [u16; CHECKSUM_LEN] {
+    let checksum = polymod(values.iter().copied().chain([0; CHECKSUM_LEN])) ^ 1;
+    [20, 10, 0].map(|shift| ((checksum >> shift) & 0x3ff) as u16)
+}
+
+/// Returns `true` if the specified 10-bit values, including the trailing
+/// checksum words, have a valid RS1024 checksum.
+pub fn verify_checksum(values: &[u16]) -> bool {
+    values.len() >= CHECKSUM_LEN && polymod(values.iter().copied()) == 1
+}
+
+/// Computes the RS1024 polynomial modulus of the values prefixed with the
+/// customization string.
+fn polymod(values: impl IntoIterator<Item = u16>) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0x00e0e040, 0x01c1c080, 0x03838100, 0x07070200, 0x0e0e0009, 0x1c0c2412, 0x38086c24,
+        0x3090fc48, 0x21b1f890, 0x03f3f120,
+    ];
+
+    let customization = CUSTOMIZATION_STRING.iter().map(|&byte| u16::from(byte));
+    customization.chain(values).fold(1, |checksum, value| {
+        let top = checksum >> 20;
+        let checksum = ((checksum & 0xfffff) << 10) ^ u32::from(value);
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 != 0)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum() {
+        for (values, checksum) in [
+            (&[0_u16; 17][..], [733, 49, 849]),
+            (
+                &[
+                    0, 50, 100, 150, 200, 250, 300, 350, 400, 450, 500, 550, 600, 650, 700, 750,
+                    800, 850, 900, 950,
+                ][..],
+                [759, 297, 950],
+            ),
+        ] {
+            assert_eq!(create_checksum(values), checksum);
+
+            let mut share = values.to_vec();
+            share.extend(checksum);
+            assert!(verify_checksum(&share));
+        }
+    }
+
+    #[test]
+    fn detects_errors() {
+        let mut share = (0..20).map(|i| i * 51 % 1024).collect::<Vec<u16>>();
+        share.extend(create_checksum(&share));
+        assert!(verify_checksum(&share));
+
+        for i in 0..share.len() {
+            for j in i..share.len() {
+                let mut corrupted = share.clone();
+                corrupted[i] ^= 0x155;
+                corrupted[j] ^= 0x2a;
+                assert!(!verify_checksum(&corrupted));
+            }
+        }
+
+        assert!(!verify_checksum(&[]));
+        assert!(!verify_checksum(&share[1..]));
+    }
+}
@@ -2,19 +2,32 @@
 //! implementation.
 
 pub mod accesslist;
+pub mod authorization;
 mod eip1559;
 mod eip2930;
+mod eip4844;
+mod eip7702;
 mod legacy;
-mod rlp;
+pub mod rlp;
 
+use self::accesslist::AccessList;
 pub use self::{
-    eip1559::Eip1559Transaction, eip2930::Eip2930Transaction, legacy::LegacyTransaction,
+    eip1559::Eip1559Transaction, eip2930::Eip2930Transaction, eip4844::Eip4844Transaction,
+    eip7702::Eip7702Transaction, legacy::LegacyTransaction,
 };
-use crate::{account::Signature, hash, serialization::JsonObject};
+use crate::{
+    account::Signature,
+    hash::{Hash32, Keccak256Hasher},
+    serialization::JsonObject,
+};
+use anyhow::{bail, ensure, Context as _, Result};
+use ethaddr::Address;
+use ethnum::U256;
 use serde::{
     de::{self, Deserializer},
     Deserialize,
 };
+use std::io::{self, Write};
 
 /// An Ethereum transaction.
 #[derive(Clone, Debug)]
@@ -22,51 +35,368 @@ pub enum Transaction {
     Legacy(LegacyTransaction),
     Eip2930(Eip2930Transaction),
     Eip1559(Eip1559Transaction),
+    Eip4844(Eip4844Transaction),
+    Eip7702(Eip7702Transaction),
 }
 
 impl Transaction {
     /// Returns the RLP encoded transaction with an optional signature.
-    pub fn signing_message(&self) -> [u8; 32] {
-        hash::keccak256(self.rlp_encode(None))
+    pub fn signing_message(&self) -> Hash32 {
+        let mut hasher = Keccak256Hasher::new();
+        self.rlp_encode_to(&mut hasher, None)
+            .expect("unexpected error writing to hasher");
+        Hash32(hasher.finalize())
     }
 
-    /// Returns the 32-byte message used for signing.
+    /// Returns the RLP encoded signed transaction.
+    ///
+    /// Signatures with high S-values are normalized, since Ethereum nodes
+    /// reject transactions with malleable signatures (see EIP-2).
     pub fn encode(&self, signature: Signature) -> Vec<u8> {
-        self.rlp_encode(Some(signature))
+        self.rlp_encode(Some(signature.to_low_s()))
+    }
+
+    /// Writes the RLP encoded signed transaction into a writer, without
+    /// buffering the encoded transaction in memory.
+    ///
+    /// Signatures are normalized the same way as [`Transaction::encode`].
+    pub fn encode_to(&self, out: &mut impl Write, signature: Signature) -> io::Result<()> {
+        self.rlp_encode_to(out, Some(signature.to_low_s()))
+    }
+
+    /// Returns the transaction hash of the signed transaction, as displayed by
+    /// block explorers. This is the hash of the RLP encoded signed transaction,
+    /// including the EIP-2718 type prefix for typed transactions.
+    pub fn hash(&self, signature: Signature) -> Hash32 {
+        let mut hasher = Keccak256Hasher::new();
+        self.encode_to(&mut hasher, signature)
+            .expect("unexpected error writing to hasher");
+        Hash32(hasher.finalize())
+    }
+
+    /// Decodes a raw signed transaction, returning the transaction along with
+    /// its signature. Both EIP-2718 typed transactions and legacy transactions
+    /// are supported.
+    pub fn decode(bytes: &[u8]) -> Result<(Self, Signature)> {
+        let (&prefix, payload) = bytes.split_first().context("empty transaction")?;
+        Ok(match prefix {
+            0x01 => {
+                let (tx, signature) = Eip2930Transaction::rlp_decode(payload)?;
+                (Transaction::Eip2930(tx), signature)
+            }
+            0x02 => {
+                let (tx, signature) = Eip1559Transaction::rlp_decode(payload)?;
+                (Transaction::Eip1559(tx), signature)
+            }
+            0x03 => {
+                let (tx, signature) = Eip4844Transaction::rlp_decode(payload)?;
+                (Transaction::Eip4844(tx), signature)
+            }
+            0x04 => {
+                let (tx, signature) = Eip7702Transaction::rlp_decode(payload)?;
+                (Transaction::Eip7702(tx), signature)
+            }
+            0xc0..=0xff => {
+                let (tx, signature) = LegacyTransaction::rlp_decode(bytes)?;
+                (Transaction::Legacy(tx), signature)
+            }
+            _ => bail!("unsupported transaction type {prefix:#04x}"),
+        })
+    }
+
+    /// Recovers the address of the account that signed the transaction.
+    ///
+    /// Note that for legacy transactions, the chain ID is part of the signing
+    /// message as specified in EIP-155. Transactions decoded with
+    /// [`Transaction::decode`] recover the chain ID from the signature's
+    /// V-value, so the signer is recovered from the same message that was
+    /// originally signed.
+    pub fn recover_signer(&self, signature: &Signature) -> Result<Address> {
+        signature.recover_address(self.signing_message())
+    }
+
+    /// Returns the chain ID of the transaction, or `None` for legacy
+    /// transactions without replay protection.
+    pub fn chain_id(&self) -> Option<U256> {
+        match self {
+            Transaction::Legacy(tx) => tx.chain_id,
+            Transaction::Eip2930(tx) => Some(tx.chain_id),
+            Transaction::Eip1559(tx) => Some(tx.chain_id),
+            Transaction::Eip4844(tx) => Some(tx.chain_id),
+            Transaction::Eip7702(tx) => Some(tx.chain_id),
+        }
+    }
+
+    /// Returns the gas limit of the transaction.
+    pub fn gas(&self) -> U256 {
+        match self {
+            Transaction::Legacy(tx) => tx.gas,
+            Transaction::Eip2930(tx) => tx.gas,
+            Transaction::Eip1559(tx) => tx.gas,
+            Transaction::Eip4844(tx) => tx.gas,
+            Transaction::Eip7702(tx) => tx.gas,
+        }
+    }
+
+    /// Returns the target address of the transaction, or `None` for contract
+    /// creation transactions.
+    pub fn to(&self) -> Option<Address> {
+        match self {
+            Transaction::Legacy(tx) => tx.to,
+            Transaction::Eip2930(tx) => tx.to,
+            Transaction::Eip1559(tx) => tx.to,
+            Transaction::Eip4844(tx) => Some(tx.to),
+            Transaction::Eip7702(tx) => Some(tx.to),
+        }
+    }
+
+    /// Returns the amount of Ether sent with the transaction.
+    pub fn value(&self) -> U256 {
+        match self {
+            Transaction::Legacy(tx) => tx.value,
+            Transaction::Eip2930(tx) => tx.value,
+            Transaction::Eip1559(tx) => tx.value,
+            Transaction::Eip4844(tx) => tx.value,
+            Transaction::Eip7702(tx) => tx.value,
+        }
+    }
+
+    /// Returns the maximum amount of Ether in Wei that the transaction can
+    /// spend. This is the value sent with the transaction plus the gas limit
+    /// times the maximum gas price, as well as the maximum blob fees for blob
+    /// transactions. Returns `None` if the computation overflows.
+    ///
+    /// This is useful for checking that an account has sufficient balance
+    /// before submitting a transaction.
+    pub fn total_cost(&self) -> Option<U256> {
+        const GAS_PER_BLOB: u128 = 1 << 17;
+
+        let (max_gas_price, blob_fees) = match self {
+            Transaction::Legacy(tx) => (tx.gas_price, U256::ZERO),
+            Transaction::Eip2930(tx) => (tx.gas_price, U256::ZERO),
+            Transaction::Eip1559(tx) => (tx.max_fee_per_gas, U256::ZERO),
+            Transaction::Eip4844(tx) => {
+                let blob_gas = U256::new(tx.blob_versioned_hashes.len() as u128 * GAS_PER_BLOB);
+                (
+                    tx.max_fee_per_gas,
+                    blob_gas.checked_mul(tx.max_fee_per_blob_gas)?,
+                )
+            }
+            Transaction::Eip7702(tx) => (tx.max_fee_per_gas, U256::ZERO),
+        };
+
+        self.gas()
+            .checked_mul(max_gas_price)?
+            .checked_add(blob_fees)?
+            .checked_add(self.value())
+    }
+
+    /// Returns `true` if the transaction sends Ether to the zero address. This
+    /// is usually a mistake that results in lost funds rather than an
+    /// intentional burn. Note that this does not apply to contract creation
+    /// transactions, which have no target address.
+    pub fn is_zero_address_transfer(&self) -> bool {
+        self.to() == Some(Address::default()) && self.value() != U256::ZERO
+    }
+
+    /// Returns `true` if the transaction specifies a chain ID of zero. This is
+    /// almost always a mistake, as such transactions are not accepted by any
+    /// public network.
+    pub fn is_zero_chain_id(&self) -> bool {
+        self.chain_id() == Some(U256::ZERO)
+    }
+
+    /// Returns the calldata of the transaction.
+    pub fn data(&self) -> &[u8] {
+        match self {
+            Transaction::Legacy(tx) => &tx.data,
+            Transaction::Eip2930(tx) => &tx.data,
+            Transaction::Eip1559(tx) => &tx.data,
+            Transaction::Eip4844(tx) => &tx.data,
+            Transaction::Eip7702(tx) => &tx.data,
+        }
+    }
+
+    /// Returns the access list of the transaction, or `None` for legacy
+    /// transactions which do not support them.
+    pub fn access_list(&self) -> Option<&AccessList> {
+        match self {
+            Transaction::Legacy(_) => None,
+            Transaction::Eip2930(tx) => Some(&tx.access_list),
+            Transaction::Eip1559(tx) => Some(&tx.access_list),
+            Transaction::Eip4844(tx) => Some(&tx.access_list),
+            Transaction::Eip7702(tx) => Some(&tx.access_list),
+        }
+    }
+
+    /// Returns a mutable reference to the access list of the transaction, or
+    /// `None` for legacy transactions which do not support them.
+    pub fn access_list_mut(&mut self) -> Option<&mut AccessList> {
+        match self {
+            Transaction::Legacy(_) => None,
+            Transaction::Eip2930(tx) => Some(&mut tx.access_list),
+            Transaction::Eip1559(tx) => Some(&mut tx.access_list),
+            Transaction::Eip4844(tx) => Some(&mut tx.access_list),
+            Transaction::Eip7702(tx) => Some(&mut tx.access_list),
+        }
+    }
+
+    /// Returns the intrinsic gas of the transaction. This is the gas that is
+    /// charged before any EVM code is executed, and is therefore a lower
+    /// bound for the transaction's gas limit.
+    pub fn intrinsic_gas(&self) -> U256 {
+        // NOTE: Gas costs from the Ethereum yellow paper, including changes
+        // from EIP-2028 (calldata gas cost reduction), EIP-2930 (access list
+        // costs), EIP-3860 (initcode word cost) and EIP-7702 (authorization
+        // costs).
+        const TX: u128 = 21_000;
+        const TX_CREATE: u128 = 32_000;
+        const TX_DATA_ZERO: u128 = 4;
+        const TX_DATA_NON_ZERO: u128 = 16;
+        const INITCODE_WORD: u128 = 2;
+        const ACCESS_LIST_ADDRESS: u128 = 2_400;
+        const ACCESS_LIST_STORAGE_KEY: u128 = 1_900;
+        const AUTHORIZATION: u128 = 25_000;
+
+        let data = self.data();
+        let zeros = data.iter().filter(|&&byte| byte == 0).count() as u128;
+        let non_zeros = data.len() as u128 - zeros;
+
+        let mut gas = TX + zeros * TX_DATA_ZERO + non_zeros * TX_DATA_NON_ZERO;
+        if self.to().is_none() {
+            let words = (data.len() as u128).div_ceil(32);
+            gas += TX_CREATE + words * INITCODE_WORD;
+        }
+        for (_, slots) in self.access_list().into_iter().flat_map(|list| &list.0) {
+            gas += ACCESS_LIST_ADDRESS + slots.len() as u128 * ACCESS_LIST_STORAGE_KEY;
+        }
+        if let Transaction::Eip7702(tx) = self {
+            gas += tx.authorization_list.len() as u128 * AUTHORIZATION;
+        }
+
+        U256::new(gas)
+    }
+
+    /// Returns `true` if the transaction's gas limit is below its intrinsic
+    /// gas, meaning that it can never be included in a block.
+    pub fn is_below_intrinsic_gas(&self) -> bool {
+        self.gas() < self.intrinsic_gas()
+    }
+
+    /// Returns the size in bytes of the RLP encoded transaction, either with
+    /// the specified signature or as a signing message when `None`. This is
+    /// useful for budgeting transaction sizes before signing, as the size of
+    /// a signed transaction only depends on the signature's encoded length.
+    pub fn rlp_size(&self, signature: Option<Signature>) -> usize {
+        let (prefix, value) = self.rlp_value(signature.map(|signature| signature.to_low_s()));
+        prefix.len() + value.size()
     }
 
     /// Returns the RLP encoded transaction with an optional signature.
     fn rlp_encode(&self, signature: Option<Signature>) -> Vec<u8> {
+        let (prefix, value) = self.rlp_value(signature);
+        let mut buf = Vec::with_capacity(prefix.len() + value.size());
+        buf.extend_from_slice(prefix);
+        value
+            .encode_to(&mut buf)
+            .expect("unexpected error writing to buffer");
+        buf
+    }
+
+    /// Writes the RLP encoded transaction with an optional signature into a
+    /// writer.
+    fn rlp_encode_to(&self, out: &mut impl Write, signature: Option<Signature>) -> io::Result<()> {
+        let (prefix, value) = self.rlp_value(signature);
+        out.write_all(prefix)?;
+        value.encode_to(out)
+    }
+
+    /// Returns the EIP-2718 type prefix (empty for legacy transactions) and
+    /// the RLP value of the transaction with an optional signature.
+    fn rlp_value(&self, signature: Option<Signature>) -> (&'static [u8], rlp::Value<'_>) {
         match self {
-            Transaction::Legacy(tx) => tx.rlp_encode(signature),
-            Transaction::Eip2930(tx) => tx.rlp_encode(signature),
-            Transaction::Eip1559(tx) => tx.rlp_encode(signature),
+            Transaction::Legacy(tx) => (&[], tx.rlp_value(signature)),
+            Transaction::Eip2930(tx) => (&[0x01], tx.rlp_value(signature)),
+            Transaction::Eip1559(tx) => (&[0x02], tx.rlp_value(signature)),
+            Transaction::Eip4844(tx) => (&[0x03], tx.rlp_value(signature)),
+            Transaction::Eip7702(tx) => (&[0x04], tx.rlp_value(signature)),
         }
     }
 }
 
+/// Decodes a signature from its y-parity and RLP encoded R and S values.
+fn decode_signature(y_parity: U256, r: rlp::Item, s: rlp::Item) -> Result<Signature> {
+    ensure!(
+        y_parity <= U256::ONE,
+        "invalid signature y-parity {y_parity}"
+    );
+    Signature::try_from_parts(
+        r.into_uint()?.to_be_bytes(),
+        s.into_uint()?.to_be_bytes(),
+        y_parity.as_u8(),
+    )
+}
+
 impl<'de> Deserialize<'de> for Transaction {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let json = JsonObject::deserialize(deserializer)?;
-        if json.contains_key("maxPriorityFeePerGas") || json.contains_key("maxFeePerGas") {
-            Ok(Transaction::Eip1559(
-                serde_json::from_value(json.into()).map_err(de::Error::custom)?,
-            ))
-        } else if json.contains_key("accessList") {
-            Ok(Transaction::Eip2930(
-                serde_json::from_value(json.into()).map_err(de::Error::custom)?,
-            ))
-        } else {
-            Ok(Transaction::Legacy(
-                serde_json::from_value(json.into()).map_err(de::Error::custom)?,
-            ))
+        let inferred = infer_type(&json);
+
+        // NOTE: An explicit EIP-2718 `type` field takes precedence over the
+        // inferred type, so that missing fields are reported for the declared
+        // transaction type. Afterwards, the fields are checked to not belong
+        // to a different transaction type.
+        let Some(kind) = json.get("type") else {
+            return from_json(inferred, json).map_err(de::Error::custom);
+        };
+        let kind: U256 = ethnum::serde::permissive::deserialize(kind).map_err(de::Error::custom)?;
+        if kind > U256::new(0x04) {
+            return Err(de::Error::custom(format!(
+                "unsupported transaction type {kind}"
+            )));
+        }
+        let kind = kind.as_u8();
+        let tx = from_json(kind, json).map_err(de::Error::custom)?;
+        if kind != inferred {
+            return Err(de::Error::custom(format!(
+                "transaction type {kind:#04x} conflicts with fields for type {inferred:#04x}"
+            )));
         }
+        Ok(tx)
+    }
+}
+
+/// Infers the EIP-2718 transaction type from the fields present in a JSON
+/// transaction object.
+fn infer_type(json: &JsonObject) -> u8 {
+    if json.contains_key("authorizationList") {
+        0x04
+    } else if json.contains_key("maxFeePerBlobGas") || json.contains_key("blobVersionedHashes") {
+        0x03
+    } else if json.contains_key("maxPriorityFeePerGas") || json.contains_key("maxFeePerGas") {
+        0x02
+    } else if json.contains_key("accessList") {
+        0x01
+    } else {
+        0x00
     }
 }
 
+/// Deserializes a JSON transaction object of the specified EIP-2718 type.
+fn from_json(kind: u8, json: JsonObject) -> serde_json::Result<Transaction> {
+    let json = json.into();
+    Ok(match kind {
+        0x01 => Transaction::Eip2930(serde_json::from_value(json)?),
+        0x02 => Transaction::Eip1559(serde_json::from_value(json)?),
+        0x03 => Transaction::Eip4844(serde_json::from_value(json)?),
+        0x04 => Transaction::Eip7702(serde_json::from_value(json)?),
+        _ => Transaction::Legacy(serde_json::from_value(json)?),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,6 +411,646 @@ mod tests {
         tx.encode(signature)
     }
 
+    #[test]
+    fn intrinsic_gas() {
+        for (tx, gas) in [
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 0,
+                    "gas": 0,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 0,
+                    "data": "0x",
+                }),
+                21_000,
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 0,
+                    "gas": 0,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 0,
+                    "data": "0x00010000",
+                }),
+                21_028,
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 0,
+                    "gas": 0,
+                    "value": 0,
+                    "data": "0x6080604052",
+                }),
+                53_082,
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 0,
+                    "maxPriorityFeePerGas": 0,
+                    "maxFeePerGas": 0,
+                    "gas": 0,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 0,
+                    "data": "0x",
+                    "accessList": [
+                        [
+                            "0x1111111111111111111111111111111111111111",
+                            [
+                                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                                "0x0000000000000000000000000000000000000000000000000000000000000001",
+                            ],
+                        ],
+                        ["0x2222222222222222222222222222222222222222", []],
+                    ],
+                }),
+                29_600,
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 0,
+                    "maxPriorityFeePerGas": 0,
+                    "maxFeePerGas": 0,
+                    "gas": 0,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 0,
+                    "data": "0x",
+                    "authorizationList": [{
+                        "chainId": 0,
+                        "address": "0x1111111111111111111111111111111111111111",
+                        "nonce": 0,
+                        "yParity": 0,
+                        "r": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                        "s": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                    }],
+                }),
+                46_000,
+            ),
+        ] {
+            let tx = serde_json::from_value::<Transaction>(tx).unwrap();
+            assert_eq!(tx.intrinsic_gas(), U256::new(gas));
+        }
+
+        let tx = serde_json::from_value::<Transaction>(json!({
+            "nonce": 0,
+            "gasPrice": 0,
+            "gas": 20999,
+            "to": "0x1111111111111111111111111111111111111111",
+            "value": 0,
+            "data": "0x",
+        }))
+        .unwrap();
+        assert!(tx.is_below_intrinsic_gas());
+
+        let tx = serde_json::from_value::<Transaction>(json!({
+            "nonce": 0,
+            "gasPrice": 0,
+            "gas": 21000,
+            "value": 0,
+            "data": "0x",
+        }))
+        .unwrap();
+        assert!(tx.is_below_intrinsic_gas());
+    }
+
+    #[test]
+    fn total_cost() {
+        for (tx, cost) in [
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 42e9,
+                    "gas": 21000,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 1e18,
+                    "data": "0x",
+                }),
+                Some(U256::new(1_000_882_000_000_000_000)),
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 0,
+                    "gasPrice": 42e9,
+                    "gas": 100000,
+                    "value": 0,
+                    "data": "0x",
+                    "accessList": [],
+                }),
+                Some(U256::new(4_200_000_000_000_000)),
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 0,
+                    "maxPriorityFeePerGas": 1e9,
+                    "maxFeePerGas": 42e9,
+                    "gas": 21000,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 1,
+                    "data": "0x",
+                }),
+                Some(U256::new(882_000_000_000_001)),
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 0,
+                    "maxPriorityFeePerGas": 1e9,
+                    "maxFeePerGas": 42e9,
+                    "gas": 21000,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 0,
+                    "data": "0x",
+                    "maxFeePerBlobGas": 1e9,
+                    "blobVersionedHashes": [
+                        "0x01aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                        "0x01bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                    ],
+                }),
+                Some(U256::new(882_000_000_000_000 + 262_144_000_000_000)),
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": "0x8000000000000000000000000000000000000000000000000000000000000000",
+                    "gas": 2,
+                    "value": 0,
+                    "data": "0x",
+                }),
+                None,
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 1,
+                    "gas": 21000,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                    "data": "0x",
+                }),
+                None,
+            ),
+        ] {
+            let tx = serde_json::from_value::<Transaction>(tx).unwrap();
+            assert_eq!(tx.total_cost(), cost);
+        }
+    }
+
+    #[test]
+    fn zero_address_transfer() {
+        for (tx, zero_address_transfer) in [
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 0,
+                    "gas": 21000,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 1,
+                    "data": "0x",
+                }),
+                true,
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 0,
+                    "gas": 21000,
+                    "to": "0x0000000000000000000000000000000000000000",
+                    "value": 0,
+                    "data": "0x",
+                }),
+                false,
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 0,
+                    "gas": 100000,
+                    "value": 1,
+                    "data": "0x6080604052",
+                }),
+                false,
+            ),
+        ] {
+            let tx = serde_json::from_value::<Transaction>(tx).unwrap();
+            assert_eq!(tx.is_zero_address_transfer(), zero_address_transfer);
+        }
+    }
+
+    #[test]
+    fn zero_chain_id() {
+        for (tx, zero_chain_id) in [
+            (
+                json!({
+                    "chainId": 0,
+                    "nonce": 0,
+                    "maxPriorityFeePerGas": 1e9,
+                    "maxFeePerGas": 42e9,
+                    "gas": 21000,
+                    "to": "0x1111111111111111111111111111111111111111",
+                    "value": 0,
+                    "data": "0x",
+                }),
+                true,
+            ),
+            (
+                json!({
+                    "chainId": 0,
+                    "nonce": 0,
+                    "gasPrice": 42e9,
+                    "gas": 21000,
+                    "to": "0x1111111111111111111111111111111111111111",
+                    "value": 0,
+                    "data": "0x",
+                    "accessList": [],
+                }),
+                true,
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 0,
+                    "maxPriorityFeePerGas": 1e9,
+                    "maxFeePerGas": 42e9,
+                    "gas": 21000,
+                    "to": "0x1111111111111111111111111111111111111111",
+                    "value": 0,
+                    "data": "0x",
+                }),
+                false,
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 42e9,
+                    "gas": 21000,
+                    "to": "0x1111111111111111111111111111111111111111",
+                    "value": 0,
+                    "data": "0x",
+                }),
+                false,
+            ),
+        ] {
+            let tx = serde_json::from_value::<Transaction>(tx).unwrap();
+            assert_eq!(tx.is_zero_chain_id(), zero_chain_id);
+        }
+    }
+
+    #[test]
+    fn decode_signed_transaction() {
+        for tx in [
+            json!({
+                "nonce": 0,
+                "gasPrice": 0,
+                "gas": 21000,
+                "to": "0x0000000000000000000000000000000000000000",
+                "value": 0,
+                "data": "0x",
+            }),
+            json!({
+                "chainId": 1,
+                "nonce": 66,
+                "gasPrice": 42e9,
+                "gas": 30000,
+                "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+                "value": 13.37e18,
+                "data": "0x01020304",
+            }),
+            json!({
+                "chainId": 1337,
+                "nonce": 777,
+                "gasPrice": 42e9,
+                "gas": 100000,
+                "value": 0,
+                "data": "0x6080604052",
+                "accessList": [
+                    [
+                        "0x1111111111111111111111111111111111111111",
+                        ["0x0000000000000000000000000000000000000000000000000000000000000001"],
+                    ],
+                ],
+            }),
+            json!({
+                "chainId": 1,
+                "nonce": 0,
+                "maxPriorityFeePerGas": 1e9,
+                "maxFeePerGas": 42e9,
+                "gas": 21000,
+                "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+                "value": 1,
+                "data": "0x",
+                "accessList": [["0x2222222222222222222222222222222222222222", []]],
+            }),
+            json!({
+                "chainId": 1,
+                "nonce": 1,
+                "maxPriorityFeePerGas": 1e9,
+                "maxFeePerGas": 42e9,
+                "gas": 21000,
+                "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+                "value": 0,
+                "data": "0x",
+                "maxFeePerBlobGas": 1e9,
+                "blobVersionedHashes": [
+                    "0x01aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                ],
+            }),
+            json!({
+                "chainId": 1,
+                "nonce": 2,
+                "maxPriorityFeePerGas": 1e9,
+                "maxFeePerGas": 42e9,
+                "gas": 100000,
+                "to": "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1",
+                "value": 0,
+                "data": "0x",
+                "authorizationList": [{
+                    "chainId": 1,
+                    "address": "0x1111111111111111111111111111111111111111",
+                    "nonce": 3,
+                    "yParity": 0,
+                    "r": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                    "s": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                }],
+            }),
+        ] {
+            let tx = serde_json::from_value::<Transaction>(tx).unwrap();
+            let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+            let signature = key.sign(tx.signing_message());
+            let encoded = tx.encode(signature);
+
+            let (decoded, decoded_signature) = Transaction::decode(&encoded).unwrap();
+            assert_eq!(decoded_signature, signature);
+            assert_eq!(
+                decoded.recover_signer(&decoded_signature).unwrap(),
+                key.address(),
+            );
+            assert_eq!(decoded.chain_id(), tx.chain_id());
+            assert_eq!(decoded.signing_message(), tx.signing_message());
+            assert_eq!(decoded.encode(decoded_signature), encoded);
+        }
+    }
+
+    #[test]
+    fn rlp_size() {
+        let signature = Signature::from_parts([0x11; 32], [0x22; 32], 1);
+        for (tx, unsigned, signed) in [
+            (
+                // Transaction from the EIP-155 example.
+                json!({
+                    "chainId": 1,
+                    "nonce": 9,
+                    "gasPrice": 20e9,
+                    "gas": 21000,
+                    "to": "0x3535353535353535353535353535353535353535",
+                    "value": 1e18,
+                    "data": "0x",
+                }),
+                45,
+                110,
+            ),
+            (
+                json!({
+                    "nonce": 0,
+                    "gasPrice": 0,
+                    "gas": 100000,
+                    "value": 0,
+                    "data": "0x6080604052",
+                }),
+                15,
+                83,
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 0,
+                    "maxPriorityFeePerGas": 1e9,
+                    "maxFeePerGas": 42e9,
+                    "gas": 21000,
+                    "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+                    "value": 1,
+                    "data": "0x01020304",
+                    "accessList": [
+                        [
+                            "0x2222222222222222222222222222222222222222",
+                            ["0x0000000000000000000000000000000000000000000000000000000000000001"],
+                        ],
+                    ],
+                }),
+                104,
+                171,
+            ),
+            (
+                json!({
+                    "chainId": 1,
+                    "nonce": 2,
+                    "maxPriorityFeePerGas": 1e9,
+                    "maxFeePerGas": 42e9,
+                    "gas": 100000,
+                    "to": "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1",
+                    "value": 0,
+                    "data": "0x",
+                    "authorizationList": [{
+                        "chainId": 1,
+                        "address": "0x1111111111111111111111111111111111111111",
+                        "nonce": 3,
+                        "yParity": 0,
+                        "r": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                        "s": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                    }],
+                }),
+                138,
+                205,
+            ),
+        ] {
+            let tx = serde_json::from_value::<Transaction>(tx).unwrap();
+            assert_eq!(tx.rlp_size(None), unsigned);
+            assert_eq!(tx.rlp_size(Some(signature)), signed);
+        }
+    }
+
+    #[test]
+    fn recover_legacy_transaction_signer() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        for encoded in [
+            hex!(
+                "f85f808082520894000000000000000000000000000000000000000080801ca0
+                 0f1c0e95b7050ac3df5ac3b69a7d41e0b815da462fcd30954b1c37b58ca71c16
+                 a068dab467ad79359967a3df1bcfc17292a3839288d05274d0e3e391f8b50841
+                 0b"
+            ),
+            hex!(
+                "f85f8080825208940000000000000000000000000000000000000000808025a0
+                 c97442e361bf3940bec722b240c699de22302469756436bbcc5a150a93309b08
+                 a02fd3e68ed327dea3d085ec16a8589ebf7871e5a990669f67be82a70cd9dfb4
+                 f7"
+            ),
+        ] {
+            let (tx, signature) = Transaction::decode(&encoded).unwrap();
+            assert_eq!(tx.recover_signer(&signature).unwrap(), key.address());
+        }
+
+        // Dropping the EIP-155 chain ID changes the signing message, and
+        // therefore the recovered signer.
+        let (tx, signature) = Transaction::decode(&hex!(
+            "f85f8080825208940000000000000000000000000000000000000000808025a0
+             c97442e361bf3940bec722b240c699de22302469756436bbcc5a150a93309b08
+             a02fd3e68ed327dea3d085ec16a8589ebf7871e5a990669f67be82a70cd9dfb4
+             f7"
+        ))
+        .unwrap();
+        let Transaction::Legacy(mut tx) = tx else {
+            panic!("expected legacy transaction");
+        };
+        assert_eq!(tx.chain_id, Some(U256::ONE));
+        tx.chain_id = None;
+        assert_ne!(
+            Transaction::Legacy(tx).recover_signer(&signature).unwrap(),
+            key.address(),
+        );
+    }
+
+    #[test]
+    fn transaction_hash() {
+        // Signed transaction from the EIP-155 example.
+        let (tx, signature) = Transaction::decode(&hex!(
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000
+             8025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d899
+             7f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        ))
+        .unwrap();
+        assert_eq!(
+            tx.hash(signature),
+            hex!("33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788"),
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        let invalid: [&[u8]; 4] = [
+            &[],
+            &[0x7f, 0xc0],
+            &hex!("02c0"),
+            &hex!(
+                "f85f808082520894000000000000000000000000000000000000000080801aa0
+                 0f1c0e95b7050ac3df5ac3b69a7d41e0b815da462fcd30954b1c37b58ca71c16
+                 a068dab467ad79359967a3df1bcfc17292a3839288d05274d0e3e391f8b50841
+                 0b"
+            ),
+        ];
+        for invalid in invalid {
+            assert!(Transaction::decode(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn explicit_transaction_type() {
+        let tx = |fields: Value| {
+            let mut tx = json!({
+                "chainId": 1,
+                "nonce": 0,
+                "gas": 21000,
+                "to": "0x0000000000000000000000000000000000000000",
+                "value": 0,
+                "data": "0x",
+            });
+            tx.as_object_mut()
+                .unwrap()
+                .extend(fields.as_object().unwrap().clone());
+            serde_json::from_value::<Transaction>(tx)
+        };
+
+        assert!(matches!(
+            tx(json!({ "type": "0x0", "gasPrice": 0 })).unwrap(),
+            Transaction::Legacy(_),
+        ));
+        assert!(matches!(
+            tx(json!({ "type": 1, "gasPrice": 0, "accessList": [] })).unwrap(),
+            Transaction::Eip2930(_),
+        ));
+        assert!(matches!(
+            tx(json!({
+                "type": "0x2",
+                "gasPrice": 0,
+                "maxPriorityFeePerGas": 0,
+                "maxFeePerGas": 0,
+                "accessList": [],
+            }))
+            .unwrap(),
+            Transaction::Eip1559(_),
+        ));
+
+        // Fields for a different transaction type.
+        assert!(tx(json!({ "type": "0x0", "gasPrice": 0, "accessList": [] })).is_err());
+        assert!(tx(json!({
+            "type": "0x1",
+            "gasPrice": 0,
+            "maxPriorityFeePerGas": 0,
+            "maxFeePerGas": 0,
+            "accessList": [],
+        }))
+        .is_err());
+        // Missing fields for the declared transaction type.
+        assert!(tx(json!({ "type": "0x2", "gasPrice": 0 })).is_err());
+        // Unsupported transaction type.
+        assert!(tx(json!({ "type": "0x5", "gasPrice": 0 })).is_err());
+
+        // Without an explicit type, it is inferred from the fields.
+        assert!(matches!(
+            tx(json!({ "gasPrice": 0 })).unwrap(),
+            Transaction::Legacy(_),
+        ));
+        assert!(matches!(
+            tx(json!({ "gasPrice": 0, "accessList": [] })).unwrap(),
+            Transaction::Eip2930(_),
+        ));
+        assert!(matches!(
+            tx(json!({ "maxPriorityFeePerGas": 0, "maxFeePerGas": 0 })).unwrap(),
+            Transaction::Eip1559(_),
+        ));
+    }
+
+    #[test]
+    fn encode_normalizes_high_s_signature() {
+        let tx = serde_json::from_value::<Transaction>(json!({
+            "chainId": 1,
+            "nonce": 0,
+            "maxPriorityFeePerGas": 0,
+            "maxFeePerGas": 0,
+            "gas": 21000,
+            "to": "0x0000000000000000000000000000000000000000",
+            "value": 0,
+            "data": "0x",
+        }))
+        .unwrap();
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let signature = key.sign(tx.signing_message());
+
+        let high_s = {
+            const N: U256 = U256::from_words(
+                0xfffffffffffffffffffffffffffffffe,
+                0xbaaedce6af48a03bbfd25e8cd0364141,
+            );
+            Signature::from_parts(
+                signature.r().to_be_bytes(),
+                (N - signature.s()).to_be_bytes(),
+                1 - signature.y_parity().as_u8(),
+            )
+        };
+        assert!(!high_s.is_low_s());
+
+        assert_eq!(tx.encode(high_s), tx.encode(signature));
+        assert_eq!(
+            high_s.recover_address(tx.signing_message()).unwrap(),
+            key.address(),
+        );
+    }
+
     #[test]
     fn encode_signed_transaction() {
         assert_eq!(
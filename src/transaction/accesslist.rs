@@ -2,8 +2,10 @@
 //! serialization implementation.
 
 use crate::{serialization, transaction::rlp};
+use anyhow::{ensure, Context as _, Result};
 use ethaddr::Address;
 use serde::Deserialize;
+use std::str::FromStr;
 
 /// An Ethereum virtual machine storage slot.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialOrd, PartialEq)]
@@ -13,7 +15,43 @@ pub struct StorageSlot(#[serde(with = "serialization::bytearray")] pub [u8; 32])
 impl StorageSlot {
     /// RLP encodes a storage slot.
     pub fn rlp_encode(&self) -> Vec<u8> {
-        rlp::bytes(&self.0)
+        self.rlp_value().encode()
+    }
+
+    /// Returns the storage slot as an RLP value.
+    pub fn rlp_value(&self) -> rlp::Value<'_> {
+        rlp::Value::Bytes(&self.0)
+    }
+
+    /// Decodes a storage slot from an RLP item.
+    pub fn rlp_decode(item: rlp::Item) -> Result<Self> {
+        let bytes = item.into_bytes()?;
+        let slot = bytes[..]
+            .try_into()
+            .with_context(|| format!("invalid storage slot length {}", bytes.len()))?;
+        Ok(Self(slot))
+    }
+}
+
+impl FromStr for StorageSlot {
+    type Err = anyhow::Error;
+
+    /// Parses a storage slot from a `0x`-prefixed hexadecimal string. Short
+    /// strings are left-padded with zeros, so that `0x1` is parsed as storage
+    /// slot 1.
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s
+            .strip_prefix("0x")
+            .with_context(|| format!("storage slot '{s}' missing '0x' prefix"))?;
+        ensure!(
+            (1..=64).contains(&digits.len()),
+            "storage slot '{s}' must have between 1 and 64 hex digits",
+        );
+
+        let mut slot = [0; 32];
+        hex::decode_to_slice(format!("{digits:0>64}"), &mut slot)
+            .with_context(|| format!("invalid storage slot '{s}'"))?;
+        Ok(Self(slot))
     }
 }
 
@@ -23,14 +61,80 @@ impl StorageSlot {
 pub struct AccessList(pub Vec<(Address, Vec<StorageSlot>)>);
 
 impl AccessList {
+    /// Parses an access list entry from a compact string representation of an
+    /// address and comma-separated storage slots, for example
+    /// `0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe:0x3,0x7`. The storage slots
+    /// are optional, and can be omitted along with the `:` separator.
+    pub fn parse_entry(s: &str) -> Result<(Address, Vec<StorageSlot>)> {
+        let (address, slots) = s.split_once(':').unwrap_or((s, ""));
+        let address = address
+            .parse()
+            .with_context(|| format!("invalid access list address '{address}'"))?;
+        let slots = slots
+            .split(',')
+            .filter(|slot| !slot.is_empty())
+            .map(str::parse)
+            .collect::<Result<_>>()?;
+        Ok((address, slots))
+    }
+
+    /// Normalizes the access list, merging entries for duplicate addresses and
+    /// sorting both addresses and storage slots without duplicates.
+    pub fn normalize(&mut self) {
+        self.0.sort_by_key(|(address, _)| **address);
+        self.0
+            .dedup_by(|(address, slots), (previous_address, previous_slots)| {
+                let duplicate = address == previous_address;
+                if duplicate {
+                    previous_slots.append(slots);
+                }
+                duplicate
+            });
+        for (_, slots) in &mut self.0 {
+            slots.sort();
+            slots.dedup();
+        }
+    }
+
     /// RLP encodes a storage slot.
     pub fn rlp_encode(&self) -> Vec<u8> {
-        rlp::iter(self.0.iter().map(|(address, slots)| {
-            rlp::list(&[
-                &rlp::bytes(&**address),
-                &rlp::iter(slots.iter().map(StorageSlot::rlp_encode)),
-            ])
-        }))
+        self.rlp_value().encode()
+    }
+
+    /// Returns the access list as an RLP value.
+    pub fn rlp_value(&self) -> rlp::Value<'_> {
+        rlp::Value::List(
+            self.0
+                .iter()
+                .map(|(address, slots)| {
+                    rlp::Value::List(vec![
+                        rlp::Value::Bytes(&**address),
+                        rlp::Value::List(slots.iter().map(StorageSlot::rlp_value).collect()),
+                    ])
+                })
+                .collect(),
+        )
+    }
+
+    /// Decodes an access list from an RLP item.
+    pub fn rlp_decode(item: rlp::Item) -> Result<Self> {
+        let entries = item
+            .into_list()?
+            .into_iter()
+            .map(|entry| -> Result<_> {
+                let [address, slots] = entry.into_array::<2>()?;
+                let address = address
+                    .into_address()?
+                    .context("missing access list address")?;
+                let slots = slots
+                    .into_list()?
+                    .into_iter()
+                    .map(StorageSlot::rlp_decode)
+                    .collect::<Result<_>>()?;
+                Ok((address, slots))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self(entries))
     }
 }
 
@@ -75,6 +179,78 @@ mod tests {
         )
     }
 
+    #[test]
+    fn parse_entry() {
+        assert_eq!(
+            AccessList::parse_entry("0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe:0x3,0x7").unwrap(),
+            (
+                address!("0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe"),
+                vec![
+                    StorageSlot(hex!(
+                        "0000000000000000000000000000000000000000000000000000000000000003"
+                    )),
+                    StorageSlot(hex!(
+                        "0000000000000000000000000000000000000000000000000000000000000007"
+                    )),
+                ],
+            ),
+        );
+        assert_eq!(
+            AccessList::parse_entry("0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413").unwrap(),
+            (
+                address!("0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413"),
+                vec![],
+            ),
+        );
+
+        for invalid in [
+            "",
+            "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe:3",
+            "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe:0x",
+            "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe:0xgg",
+            "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe:0x\
+             00000000000000000000000000000000000000000000000000000000000000001",
+        ] {
+            assert!(AccessList::parse_entry(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn normalize() {
+        let mut access_list = AccessList(vec![
+            (
+                address!("0x2222222222222222222222222222222222222222"),
+                vec![StorageSlot([2; 32]), StorageSlot([1; 32])],
+            ),
+            (
+                address!("0x1111111111111111111111111111111111111111"),
+                vec![],
+            ),
+            (
+                address!("0x2222222222222222222222222222222222222222"),
+                vec![StorageSlot([1; 32]), StorageSlot([3; 32])],
+            ),
+        ]);
+        access_list.normalize();
+        assert_eq!(
+            access_list,
+            AccessList(vec![
+                (
+                    address!("0x1111111111111111111111111111111111111111"),
+                    vec![]
+                ),
+                (
+                    address!("0x2222222222222222222222222222222222222222"),
+                    vec![
+                        StorageSlot([1; 32]),
+                        StorageSlot([2; 32]),
+                        StorageSlot([3; 32]),
+                    ],
+                ),
+            ]),
+        );
+    }
+
     #[test]
     fn rlp_encode() {
         assert_eq!(
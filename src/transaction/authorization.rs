@@ -0,0 +1,232 @@
+//! Module with EIP-7702 authorization type definitions with RLP encoding and
+//! JSON serialization implementation.
+
+use crate::{
+    account::Signature,
+    hash::{self, Hash32},
+    transaction::{self, rlp},
+};
+use anyhow::{Context as _, Result};
+use ethaddr::Address;
+use ethnum::U256;
+use serde::{
+    de::{self, Deserializer},
+    Deserialize,
+};
+
+/// An EIP-7702 authorization for setting the code of the signing account to
+/// delegate to the code at the specified address.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Authorization {
+    /// The chain ID the authorization is valid for, or 0 for all chains.
+    #[serde(rename = "chainId", with = "ethnum::serde::permissive")]
+    pub chain_id: U256,
+    /// The address of the code to delegate to.
+    pub address: Address,
+    /// The nonce of the authorizing account.
+    #[serde(with = "ethnum::serde::permissive")]
+    pub nonce: U256,
+}
+
+impl Authorization {
+    /// Returns the message digest for signing the authorization, that is
+    /// `keccak256(0x05 || rlp([chain_id, address, nonce]))`.
+    pub fn signing_message(&self) -> Hash32 {
+        let fields = rlp::Value::List(self.rlp_fields().into());
+        hash::keccak256([&[0x05][..], &fields.encode()].concat())
+    }
+
+    /// Returns the authorization fields as RLP values.
+    fn rlp_fields(&self) -> [rlp::Value<'_>; 3] {
+        [
+            rlp::Value::Uint(self.chain_id),
+            rlp::Value::Bytes(&*self.address),
+            rlp::Value::Uint(self.nonce),
+        ]
+    }
+}
+
+/// An EIP-7702 authorization along with its signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedAuthorization {
+    /// The signed authorization.
+    pub authorization: Authorization,
+    /// The signature of the authorizing account.
+    pub signature: Signature,
+}
+
+impl SignedAuthorization {
+    /// Recovers the address of the account that signed the authorization.
+    pub fn recover_authority(&self) -> Result<Address> {
+        self.signature
+            .recover_address(self.authorization.signing_message())
+    }
+
+    /// RLP encodes a signed authorization.
+    pub fn rlp_encode(&self) -> Vec<u8> {
+        self.rlp_value().encode()
+    }
+
+    /// Returns the signed authorization as an RLP value.
+    pub fn rlp_value(&self) -> rlp::Value<'_> {
+        let signature = [
+            rlp::Value::Uint(self.signature.y_parity()),
+            rlp::Value::Uint(self.signature.r()),
+            rlp::Value::Uint(self.signature.s()),
+        ];
+        rlp::Value::List(
+            self.authorization
+                .rlp_fields()
+                .into_iter()
+                .chain(signature)
+                .collect(),
+        )
+    }
+
+    /// Decodes a signed authorization from an RLP item.
+    pub fn rlp_decode(item: rlp::Item) -> Result<Self> {
+        let [chain_id, address, nonce, y_parity, r, s] = item.into_array::<6>()?;
+        Ok(Self {
+            authorization: Authorization {
+                chain_id: chain_id.into_uint()?,
+                address: address
+                    .into_address()?
+                    .context("missing authorization address")?,
+                nonce: nonce.into_uint()?,
+            },
+            signature: transaction::decode_signature(y_parity.into_uint()?, r, s)?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SignedAuthorization {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Json {
+            #[serde(rename = "chainId", with = "ethnum::serde::permissive")]
+            chain_id: U256,
+            address: Address,
+            #[serde(with = "ethnum::serde::permissive")]
+            nonce: U256,
+            #[serde(rename = "yParity", with = "ethnum::serde::permissive")]
+            y_parity: U256,
+            #[serde(with = "ethnum::serde::permissive")]
+            r: U256,
+            #[serde(with = "ethnum::serde::permissive")]
+            s: U256,
+        }
+
+        let json = Json::deserialize(deserializer)?;
+        if json.y_parity > U256::ONE {
+            return Err(de::Error::custom(format!(
+                "invalid authorization y-parity {}",
+                json.y_parity,
+            )));
+        }
+        let signature = Signature::try_from_parts(
+            json.r.to_be_bytes(),
+            json.s.to_be_bytes(),
+            json.y_parity.as_u8(),
+        )
+        .map_err(de::Error::custom)?;
+
+        Ok(Self {
+            authorization: Authorization {
+                chain_id: json.chain_id,
+                address: json.address,
+                nonce: json.nonce,
+            },
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account::PrivateKey, ganache::DETERMINISTIC_PRIVATE_KEY};
+    use ethaddr::address;
+    use hex_literal::hex;
+    use serde_json::json;
+
+    #[test]
+    fn signing_message() {
+        for (authorization, message) in [
+            (
+                Authorization {
+                    chain_id: U256::ONE,
+                    address: address!("0x1111111111111111111111111111111111111111"),
+                    nonce: U256::ZERO,
+                },
+                hex!("d35655e0048045ff05a91979004fc1afa8325086bbab53c2498ecfcfe431d99f"),
+            ),
+            (
+                Authorization {
+                    chain_id: U256::ZERO,
+                    address: address!("0x1111111111111111111111111111111111111111"),
+                    nonce: U256::new(42),
+                },
+                hex!("01af8ad093f49fe969ffb7532335fea63dd5ec14b1d4defb0c181c8422fd0300"),
+            ),
+        ] {
+            assert_eq!(authorization.signing_message(), message);
+        }
+    }
+
+    #[test]
+    fn sign_and_recover() {
+        let key = PrivateKey::new(DETERMINISTIC_PRIVATE_KEY).unwrap();
+        let authorization = Authorization {
+            chain_id: U256::ONE,
+            address: address!("0x1111111111111111111111111111111111111111"),
+            nonce: U256::new(7),
+        };
+        let signed = SignedAuthorization {
+            signature: key.sign(authorization.signing_message()),
+            authorization,
+        };
+
+        assert_eq!(signed.recover_authority().unwrap(), key.address());
+        assert_eq!(
+            SignedAuthorization::rlp_decode(rlp::decode(&signed.rlp_encode()).unwrap().0).unwrap(),
+            signed,
+        );
+    }
+
+    #[test]
+    fn deserialize_json() {
+        let signed = serde_json::from_value::<SignedAuthorization>(json!({
+            "chainId": "0x1",
+            "address": "0x1111111111111111111111111111111111111111",
+            "nonce": 7,
+            "yParity": "0x1",
+            "r": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "s": "0x2222222222222222222222222222222222222222222222222222222222222222",
+        }))
+        .unwrap();
+        assert_eq!(
+            signed,
+            SignedAuthorization {
+                authorization: Authorization {
+                    chain_id: U256::ONE,
+                    address: address!("0x1111111111111111111111111111111111111111"),
+                    nonce: U256::new(7),
+                },
+                signature: Signature::from_parts([0x11; 32], [0x22; 32], 1),
+            },
+        );
+
+        assert!(serde_json::from_value::<SignedAuthorization>(json!({
+            "chainId": 1,
+            "address": "0x1111111111111111111111111111111111111111",
+            "nonce": 7,
+            "yParity": 2,
+            "r": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "s": "0x2222222222222222222222222222222222222222222222222222222222222222",
+        }))
+        .is_err());
+    }
+}
@@ -1,8 +1,11 @@
 //! EIp-1559 Ethereum transaction with base gas pricing type definition and RLP encoding.
 
 use crate::{
-    account::Signature, serialization, transaction::accesslist::AccessList, transaction::rlp,
+    account::Signature,
+    serialization,
+    transaction::{self, accesslist::AccessList, rlp},
 };
+use anyhow::Result;
 use ethaddr::Address;
 use ethnum::U256;
 use serde::Deserialize;
@@ -43,32 +46,62 @@ pub struct Eip1559Transaction {
 impl Eip1559Transaction {
     /// Returns the RLP encoded transaction with an optional signature.
     pub fn rlp_encode(&self, signature: Option<Signature>) -> Vec<u8> {
+        [&[0x02][..], &self.rlp_value(signature).encode()].concat()
+    }
+
+    /// Returns the transaction with an optional signature as an RLP value,
+    /// without its EIP-2718 type prefix.
+    pub fn rlp_value(&self, signature: Option<Signature>) -> rlp::Value<'_> {
         let fields = [
-            rlp::uint(self.chain_id),
-            rlp::uint(self.nonce),
-            rlp::uint(self.max_priority_fee_per_gas),
-            rlp::uint(self.max_fee_per_gas),
-            rlp::uint(self.gas),
+            rlp::Value::Uint(self.chain_id),
+            rlp::Value::Uint(self.nonce),
+            rlp::Value::Uint(self.max_priority_fee_per_gas),
+            rlp::Value::Uint(self.max_fee_per_gas),
+            rlp::Value::Uint(self.gas),
             self.to
-                .map_or_else(|| rlp::bytes(b""), |to| rlp::bytes(&*to)),
-            rlp::uint(self.value),
-            rlp::bytes(&self.data),
-            self.access_list.rlp_encode(),
+                .as_ref()
+                .map_or(rlp::Value::Bytes(b""), |to| rlp::Value::Bytes(&**to)),
+            rlp::Value::Uint(self.value),
+            rlp::Value::Bytes(&self.data),
+            self.access_list.rlp_value(),
         ];
 
         let tail = signature.map(|signature| {
             [
-                rlp::uint(signature.y_parity()),
-                rlp::uint(signature.r()),
-                rlp::uint(signature.s()),
+                rlp::Value::Uint(signature.y_parity()),
+                rlp::Value::Uint(signature.r()),
+                rlp::Value::Uint(signature.s()),
             ]
         });
 
-        [
-            &[0x02][..],
-            &rlp::iter(fields.iter().chain(tail.iter().flatten())),
-        ]
-        .concat()
+        rlp::Value::List(
+            fields
+                .into_iter()
+                .chain(tail.into_iter().flatten())
+                .collect(),
+        )
+    }
+
+    /// Decodes an RLP encoded signed transaction without its type prefix.
+    pub fn rlp_decode(input: &[u8]) -> Result<(Self, Signature)> {
+        let [fields @ .., y_parity, r, s] = rlp::decode_array::<12>(input)?;
+        let [chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, fields @ ..] = fields;
+        let [gas, to, value, data, access_list] = fields;
+
+        let tx = Self {
+            chain_id: chain_id.into_uint()?,
+            nonce: nonce.into_uint()?,
+            max_priority_fee_per_gas: max_priority_fee_per_gas.into_uint()?,
+            max_fee_per_gas: max_fee_per_gas.into_uint()?,
+            gas: gas.into_uint()?,
+            to: to.into_address()?,
+            value: value.into_uint()?,
+            data: data.into_bytes()?,
+            access_list: AccessList::rlp_decode(access_list)?,
+        };
+        let signature = transaction::decode_signature(y_parity.into_uint()?, r, s)?;
+
+        Ok((tx, signature))
     }
 }
 
@@ -1,8 +1,11 @@
 //! EIp-2930 Ethereum transaction with access list type definition and RLP encoding.
 
 use crate::{
-    account::Signature, serialization, transaction::accesslist::AccessList, transaction::rlp,
+    account::Signature,
+    serialization,
+    transaction::{self, accesslist::AccessList, rlp},
 };
+use anyhow::Result;
 use ethaddr::Address;
 use ethnum::U256;
 use serde::Deserialize;
@@ -39,31 +42,59 @@ pub struct Eip2930Transaction {
 impl Eip2930Transaction {
     /// Returns the RLP encoded transaction with an optional signature.
     pub fn rlp_encode(&self, signature: Option<Signature>) -> Vec<u8> {
+        [&[0x01][..], &self.rlp_value(signature).encode()].concat()
+    }
+
+    /// Returns the transaction with an optional signature as an RLP value,
+    /// without its EIP-2718 type prefix.
+    pub fn rlp_value(&self, signature: Option<Signature>) -> rlp::Value<'_> {
         let fields = [
-            rlp::uint(self.chain_id),
-            rlp::uint(self.nonce),
-            rlp::uint(self.gas_price),
-            rlp::uint(self.gas),
+            rlp::Value::Uint(self.chain_id),
+            rlp::Value::Uint(self.nonce),
+            rlp::Value::Uint(self.gas_price),
+            rlp::Value::Uint(self.gas),
             self.to
-                .map_or_else(|| rlp::bytes(b""), |to| rlp::bytes(&*to)),
-            rlp::uint(self.value),
-            rlp::bytes(&self.data),
-            self.access_list.rlp_encode(),
+                .as_ref()
+                .map_or(rlp::Value::Bytes(b""), |to| rlp::Value::Bytes(&**to)),
+            rlp::Value::Uint(self.value),
+            rlp::Value::Bytes(&self.data),
+            self.access_list.rlp_value(),
         ];
 
         let tail = signature.map(|signature| {
             [
-                rlp::uint(signature.y_parity()),
-                rlp::uint(signature.r()),
-                rlp::uint(signature.s()),
+                rlp::Value::Uint(signature.y_parity()),
+                rlp::Value::Uint(signature.r()),
+                rlp::Value::Uint(signature.s()),
             ]
         });
 
-        [
-            &[0x01][..],
-            &rlp::iter(fields.iter().chain(tail.iter().flatten())),
-        ]
-        .concat()
+        rlp::Value::List(
+            fields
+                .into_iter()
+                .chain(tail.into_iter().flatten())
+                .collect(),
+        )
+    }
+
+    /// Decodes an RLP encoded signed transaction without its type prefix.
+    pub fn rlp_decode(input: &[u8]) -> Result<(Self, Signature)> {
+        let [chain_id, nonce, gas_price, gas, to, value, data, access_list, y_parity, r, s] =
+            rlp::decode_array::<11>(input)?;
+
+        let tx = Self {
+            chain_id: chain_id.into_uint()?,
+            nonce: nonce.into_uint()?,
+            gas_price: gas_price.into_uint()?,
+            gas: gas.into_uint()?,
+            to: to.into_address()?,
+            value: value.into_uint()?,
+            data: data.into_bytes()?,
+            access_list: AccessList::rlp_decode(access_list)?,
+        };
+        let signature = transaction::decode_signature(y_parity.into_uint()?, r, s)?;
+
+        Ok((tx, signature))
     }
 }
 
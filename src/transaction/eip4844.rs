@@ -0,0 +1,227 @@
+//! EIP-4844 Ethereum blob transaction type definition and RLP encoding.
+//!
+//! Note that this only covers the transaction itself and not the blob sidecar
+//! (blobs, commitments and proofs) that accompanies it on the network, as the
+//! sidecar is not part of the transaction's signing message.
+
+use crate::{
+    account::Signature,
+    hash::Hash32,
+    serialization,
+    transaction::{self, accesslist::AccessList, rlp},
+};
+use anyhow::{Context as _, Result};
+use ethaddr::Address;
+use ethnum::U256;
+use serde::Deserialize;
+
+/// An EIP-4844 Ethereum blob transaction.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Eip4844Transaction {
+    /// The chain ID for the transaction.
+    #[serde(rename = "chainId", with = "ethnum::serde::permissive")]
+    pub chain_id: U256,
+    /// The nonce for the transaction.
+    #[serde(with = "ethnum::serde::permissive")]
+    pub nonce: U256,
+    /// The maximum priority fee in Wei for the transaction.
+    #[serde(rename = "maxPriorityFeePerGas", with = "ethnum::serde::permissive")]
+    pub max_priority_fee_per_gas: U256,
+    /// The maximum gas price in Wei for the transaction.
+    #[serde(rename = "maxFeePerGas", with = "ethnum::serde::permissive")]
+    pub max_fee_per_gas: U256,
+    /// The gas limit for the transaction.
+    #[serde(with = "ethnum::serde::permissive")]
+    pub gas: U256,
+    /// The target address for the transaction. Note that blob transactions
+    /// cannot be used for contract creation.
+    pub to: Address,
+    /// The amount of Ether to send with the transaction.
+    #[serde(with = "ethnum::serde::permissive")]
+    pub value: U256,
+    /// The calldata to use for the transaction.
+    #[serde(with = "serialization::bytes")]
+    pub data: Vec<u8>,
+    /// List of addresses and storage keys that the transaction plans to access.
+    #[serde(default)]
+    #[serde(rename = "accessList")]
+    pub access_list: AccessList,
+    /// The maximum blob gas price in Wei for the transaction.
+    #[serde(rename = "maxFeePerBlobGas", with = "ethnum::serde::permissive")]
+    pub max_fee_per_blob_gas: U256,
+    /// The versioned hashes of the blobs included with the transaction.
+    #[serde(rename = "blobVersionedHashes")]
+    pub blob_versioned_hashes: Vec<Hash32>,
+}
+
+impl Eip4844Transaction {
+    /// Returns the RLP encoded transaction with an optional signature.
+    pub fn rlp_encode(&self, signature: Option<Signature>) -> Vec<u8> {
+        [&[0x03][..], &self.rlp_value(signature).encode()].concat()
+    }
+
+    /// Returns the transaction with an optional signature as an RLP value,
+    /// without its EIP-2718 type prefix.
+    pub fn rlp_value(&self, signature: Option<Signature>) -> rlp::Value<'_> {
+        let fields = [
+            rlp::Value::Uint(self.chain_id),
+            rlp::Value::Uint(self.nonce),
+            rlp::Value::Uint(self.max_priority_fee_per_gas),
+            rlp::Value::Uint(self.max_fee_per_gas),
+            rlp::Value::Uint(self.gas),
+            rlp::Value::Bytes(&*self.to),
+            rlp::Value::Uint(self.value),
+            rlp::Value::Bytes(&self.data),
+            self.access_list.rlp_value(),
+            rlp::Value::Uint(self.max_fee_per_blob_gas),
+            rlp::Value::List(
+                self.blob_versioned_hashes
+                    .iter()
+                    .map(|hash| rlp::Value::Bytes(&**hash))
+                    .collect(),
+            ),
+        ];
+
+        let tail = signature.map(|signature| {
+            [
+                rlp::Value::Uint(signature.y_parity()),
+                rlp::Value::Uint(signature.r()),
+                rlp::Value::Uint(signature.s()),
+            ]
+        });
+
+        rlp::Value::List(
+            fields
+                .into_iter()
+                .chain(tail.into_iter().flatten())
+                .collect(),
+        )
+    }
+
+    /// Decodes an RLP encoded signed transaction without its type prefix.
+    pub fn rlp_decode(input: &[u8]) -> Result<(Self, Signature)> {
+        let [fields @ .., y_parity, r, s] = rlp::decode_array::<14>(input)?;
+        let [chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, fields @ ..] = fields;
+        let [gas, to, value, data, access_list, max_fee_per_blob_gas, blob_versioned_hashes] =
+            fields;
+
+        let tx = Self {
+            chain_id: chain_id.into_uint()?,
+            nonce: nonce.into_uint()?,
+            max_priority_fee_per_gas: max_priority_fee_per_gas.into_uint()?,
+            max_fee_per_gas: max_fee_per_gas.into_uint()?,
+            gas: gas.into_uint()?,
+            to: to
+                .into_address()?
+                .context("blob transaction missing target address")?,
+            value: value.into_uint()?,
+            data: data.into_bytes()?,
+            access_list: AccessList::rlp_decode(access_list)?,
+            max_fee_per_blob_gas: max_fee_per_blob_gas.into_uint()?,
+            blob_versioned_hashes: blob_versioned_hashes
+                .into_list()?
+                .into_iter()
+                .map(|hash| -> Result<_> {
+                    let bytes = hash.into_bytes()?;
+                    let hash = <[u8; 32]>::try_from(&bytes[..]).with_context(|| {
+                        format!("invalid blob versioned hash length {}", bytes.len())
+                    })?;
+                    Ok(Hash32(hash))
+                })
+                .collect::<Result<_>>()?,
+        };
+        let signature = transaction::decode_signature(y_parity.into_uint()?, r, s)?;
+
+        Ok((tx, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::accesslist::StorageSlot;
+    use ethaddr::address;
+    use ethnum::AsU256 as _;
+    use hex_literal::hex;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_json() {
+        let mut tx = json!({
+            "chainId": "0xff",
+            "nonce": 42,
+            "maxPriorityFeePerGas": 13.37e9,
+            "maxFeePerGas": 42e9,
+            "gas": 21000,
+            "to": "0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+            "value": "0",
+            "data": "0x",
+            "maxFeePerBlobGas": 1e9,
+            "blobVersionedHashes": [
+                "0x01aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            ],
+        });
+        assert_eq!(
+            serde_json::from_value::<Eip4844Transaction>(tx.clone()).unwrap(),
+            Eip4844Transaction {
+                chain_id: 255.as_u256(),
+                nonce: 42.as_u256(),
+                max_priority_fee_per_gas: 13.37e9.as_u256(),
+                max_fee_per_gas: 42e9.as_u256(),
+                gas: 21_000.as_u256(),
+                to: address!("0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"),
+                value: 0.as_u256(),
+                data: vec![],
+                access_list: AccessList::default(),
+                max_fee_per_blob_gas: 1e9.as_u256(),
+                blob_versioned_hashes: vec![Hash32(hex!(
+                    "01aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                ))],
+            }
+        );
+
+        tx.as_object_mut().unwrap().remove("to");
+        assert!(serde_json::from_value::<Eip4844Transaction>(tx).is_err());
+    }
+
+    #[test]
+    fn encode() {
+        assert_eq!(
+            Eip4844Transaction {
+                chain_id: 1.as_u256(),
+                nonce: 42.as_u256(),
+                max_priority_fee_per_gas: 1e9.as_u256(),
+                max_fee_per_gas: 42e9.as_u256(),
+                gas: 21_000.as_u256(),
+                to: address!("0xdEDEDEDEdEdEdEDedEDeDedEdEdeDedEdEDedEdE"),
+                value: 0.as_u256(),
+                data: vec![],
+                access_list: AccessList(vec![(
+                    address!("0x1111111111111111111111111111111111111111"),
+                    vec![StorageSlot(hex!(
+                        "0000000000000000000000000000000000000000000000000000000000000001"
+                    ))],
+                )]),
+                max_fee_per_blob_gas: 1e9.as_u256(),
+                blob_versioned_hashes: vec![
+                    Hash32(hex!(
+                        "01aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                    )),
+                    Hash32(hex!(
+                        "01bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+                    )),
+                ],
+            }
+            .rlp_encode(None),
+            hex!(
+                "03f8aa012a843b9aca008509c765240082520894dededededededededededede
+                 dededededededede8080f838f794111111111111111111111111111111111111
+                 1111e1a000000000000000000000000000000000000000000000000000000000
+                 00000001843b9aca00f842a001aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+                 aaaaaaaaaaaaaaaaaaaaaaaaa001bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb
+                 bbbbbbbbbbbbbbbbbbbbbbbbbb"
+            )
+            .to_vec(),
+        );
+    }
+}
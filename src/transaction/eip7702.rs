@@ -0,0 +1,209 @@
+//! EIP-7702 Ethereum set code transaction type definition and RLP encoding.
+
+use crate::{
+    account::Signature,
+    serialization,
+    transaction::{self, accesslist::AccessList, authorization::SignedAuthorization, rlp},
+};
+use anyhow::{Context as _, Result};
+use ethaddr::Address;
+use ethnum::U256;
+use serde::Deserialize;
+
+/// An EIP-7702 Ethereum set code transaction.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Eip7702Transaction {
+    /// The chain ID for the transaction.
+    #[serde(rename = "chainId", with = "ethnum::serde::permissive")]
+    pub chain_id: U256,
+    /// The nonce for the transaction.
+    #[serde(with = "ethnum::serde::permissive")]
+    pub nonce: U256,
+    /// The maximum priority fee in Wei for the transaction.
+    #[serde(rename = "maxPriorityFeePerGas", with = "ethnum::serde::permissive")]
+    pub max_priority_fee_per_gas: U256,
+    /// The maximum gas price in Wei for the transaction.
+    #[serde(rename = "maxFeePerGas", with = "ethnum::serde::permissive")]
+    pub max_fee_per_gas: U256,
+    /// The gas limit for the transaction.
+    #[serde(with = "ethnum::serde::permissive")]
+    pub gas: U256,
+    /// The target address for the transaction. Note that set code
+    /// transactions cannot be used for contract creation.
+    pub to: Address,
+    /// The amount of Ether to send with the transaction.
+    #[serde(with = "ethnum::serde::permissive")]
+    pub value: U256,
+    /// The calldata to use for the transaction.
+    #[serde(with = "serialization::bytes")]
+    pub data: Vec<u8>,
+    /// List of addresses and storage keys that the transaction plans to access.
+    #[serde(default)]
+    #[serde(rename = "accessList")]
+    pub access_list: AccessList,
+    /// List of signed authorizations for setting the code of the authorizing
+    /// accounts.
+    #[serde(rename = "authorizationList")]
+    pub authorization_list: Vec<SignedAuthorization>,
+}
+
+impl Eip7702Transaction {
+    /// Returns the RLP encoded transaction with an optional signature.
+    pub fn rlp_encode(&self, signature: Option<Signature>) -> Vec<u8> {
+        [&[0x04][..], &self.rlp_value(signature).encode()].concat()
+    }
+
+    /// Returns the transaction with an optional signature as an RLP value,
+    /// without its EIP-2718 type prefix.
+    pub fn rlp_value(&self, signature: Option<Signature>) -> rlp::Value<'_> {
+        let fields = [
+            rlp::Value::Uint(self.chain_id),
+            rlp::Value::Uint(self.nonce),
+            rlp::Value::Uint(self.max_priority_fee_per_gas),
+            rlp::Value::Uint(self.max_fee_per_gas),
+            rlp::Value::Uint(self.gas),
+            rlp::Value::Bytes(&*self.to),
+            rlp::Value::Uint(self.value),
+            rlp::Value::Bytes(&self.data),
+            self.access_list.rlp_value(),
+            rlp::Value::List(
+                self.authorization_list
+                    .iter()
+                    .map(SignedAuthorization::rlp_value)
+                    .collect(),
+            ),
+        ];
+
+        let tail = signature.map(|signature| {
+            [
+                rlp::Value::Uint(signature.y_parity()),
+                rlp::Value::Uint(signature.r()),
+                rlp::Value::Uint(signature.s()),
+            ]
+        });
+
+        rlp::Value::List(
+            fields
+                .into_iter()
+                .chain(tail.into_iter().flatten())
+                .collect(),
+        )
+    }
+
+    /// Decodes an RLP encoded signed transaction without its type prefix.
+    pub fn rlp_decode(input: &[u8]) -> Result<(Self, Signature)> {
+        let [fields @ .., y_parity, r, s] = rlp::decode_array::<13>(input)?;
+        let [chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, fields @ ..] = fields;
+        let [gas, to, value, data, access_list, authorization_list] = fields;
+
+        let tx = Self {
+            chain_id: chain_id.into_uint()?,
+            nonce: nonce.into_uint()?,
+            max_priority_fee_per_gas: max_priority_fee_per_gas.into_uint()?,
+            max_fee_per_gas: max_fee_per_gas.into_uint()?,
+            gas: gas.into_uint()?,
+            to: to
+                .into_address()?
+                .context("set code transaction missing target address")?,
+            value: value.into_uint()?,
+            data: data.into_bytes()?,
+            access_list: AccessList::rlp_decode(access_list)?,
+            authorization_list: authorization_list
+                .into_list()?
+                .into_iter()
+                .map(SignedAuthorization::rlp_decode)
+                .collect::<Result<_>>()?,
+        };
+        let signature = transaction::decode_signature(y_parity.into_uint()?, r, s)?;
+
+        Ok((tx, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::authorization::Authorization;
+    use ethaddr::address;
+    use ethnum::AsU256 as _;
+    use hex_literal::hex;
+    use serde_json::json;
+
+    fn authorization() -> SignedAuthorization {
+        SignedAuthorization {
+            authorization: Authorization {
+                chain_id: 1.as_u256(),
+                address: address!("0x1111111111111111111111111111111111111111"),
+                nonce: 7.as_u256(),
+            },
+            signature: Signature::from_parts([0x11; 32], [0x22; 32], 1),
+        }
+    }
+
+    #[test]
+    fn deserialize_json() {
+        let mut tx = json!({
+            "chainId": "0xff",
+            "nonce": 42,
+            "maxPriorityFeePerGas": 13.37e9,
+            "maxFeePerGas": 42e9,
+            "gas": 100000,
+            "to": "0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+            "value": "0",
+            "data": "0x",
+            "authorizationList": [{
+                "chainId": 1,
+                "address": "0x1111111111111111111111111111111111111111",
+                "nonce": 7,
+                "yParity": 1,
+                "r": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                "s": "0x2222222222222222222222222222222222222222222222222222222222222222",
+            }],
+        });
+        assert_eq!(
+            serde_json::from_value::<Eip7702Transaction>(tx.clone()).unwrap(),
+            Eip7702Transaction {
+                chain_id: 255.as_u256(),
+                nonce: 42.as_u256(),
+                max_priority_fee_per_gas: 13.37e9.as_u256(),
+                max_fee_per_gas: 42e9.as_u256(),
+                gas: 100_000.as_u256(),
+                to: address!("0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"),
+                value: 0.as_u256(),
+                data: vec![],
+                access_list: AccessList::default(),
+                authorization_list: vec![authorization()],
+            }
+        );
+
+        tx.as_object_mut().unwrap().remove("to");
+        assert!(serde_json::from_value::<Eip7702Transaction>(tx).is_err());
+    }
+
+    #[test]
+    fn encode() {
+        assert_eq!(
+            Eip7702Transaction {
+                chain_id: 1.as_u256(),
+                nonce: 3.as_u256(),
+                max_priority_fee_per_gas: 1e9.as_u256(),
+                max_fee_per_gas: 42e9.as_u256(),
+                gas: 100_000.as_u256(),
+                to: address!("0xdEDEDEDEdEdEdEDedEDeDedEdEdeDedEdEDedEdE"),
+                value: 0.as_u256(),
+                data: vec![],
+                access_list: AccessList::default(),
+                authorization_list: vec![authorization()],
+            }
+            .rlp_encode(None),
+            hex!(
+                "04f8870103843b9aca008509c7652400830186a094dedededededededededede
+                 dedededededededede8080c0f85cf85a01941111111111111111111111111111
+                 1111111111110701a01111111111111111111111111111111111111111111111
+                 111111111111111111a022222222222222222222222222222222222222222222
+                 22222222222222222222"
+            )
+            .to_vec(),
+        );
+    }
+}
@@ -1,6 +1,11 @@
 //! Legacy Ethereum transaction type definition and RLP encoding.
 
-use crate::{account::Signature, serialization, transaction::rlp};
+use crate::{
+    account::Signature,
+    serialization,
+    transaction::{self, rlp},
+};
+use anyhow::{bail, Result};
 use ethaddr::Address;
 use ethnum::U256;
 use serde::Deserialize;
@@ -34,22 +39,67 @@ pub struct LegacyTransaction {
 impl LegacyTransaction {
     /// Returns the RLP encoded transaction with an optional signature.
     pub fn rlp_encode(&self, signature: Option<Signature>) -> Vec<u8> {
+        self.rlp_value(signature).encode()
+    }
+
+    /// Returns the transaction with an optional signature as an RLP value.
+    pub fn rlp_value(&self, signature: Option<Signature>) -> rlp::Value<'_> {
         let fields = [
-            rlp::uint(self.nonce),
-            rlp::uint(self.gas_price),
-            rlp::uint(self.gas),
+            rlp::Value::Uint(self.nonce),
+            rlp::Value::Uint(self.gas_price),
+            rlp::Value::Uint(self.gas),
             self.to
-                .map_or_else(|| rlp::bytes(b""), |to| rlp::bytes(&*to)),
-            rlp::uint(self.value),
-            rlp::bytes(&self.data),
+                .as_ref()
+                .map_or(rlp::Value::Bytes(b""), |to| rlp::Value::Bytes(&**to)),
+            rlp::Value::Uint(self.value),
+            rlp::Value::Bytes(&self.data),
         ];
 
         let tail = signature
             .map(|signature| (signature.v(self.chain_id), signature.r(), signature.s()))
             .or_else(|| Some((self.chain_id?, U256::ZERO, U256::ZERO)))
-            .map(|(v, r, s)| [rlp::uint(v), rlp::uint(r), rlp::uint(s)]);
+            .map(|(v, r, s)| {
+                [
+                    rlp::Value::Uint(v),
+                    rlp::Value::Uint(r),
+                    rlp::Value::Uint(s),
+                ]
+            });
+
+        rlp::Value::List(
+            fields
+                .into_iter()
+                .chain(tail.into_iter().flatten())
+                .collect(),
+        )
+    }
+
+    /// Decodes an RLP encoded signed transaction. The chain ID is recovered
+    /// from the signature's V-value as specified in EIP-155.
+    pub fn rlp_decode(input: &[u8]) -> Result<(Self, Signature)> {
+        let [nonce, gas_price, gas, to, value, data, v, r, s] = rlp::decode_array::<9>(input)?;
+
+        let v = v.into_uint()?;
+        let (chain_id, y_parity) = if v == U256::new(27) || v == U256::new(28) {
+            (None, v - 27)
+        } else if v >= U256::new(35) {
+            (Some((v - 35) / 2), (v - 35) % 2)
+        } else {
+            bail!("invalid legacy transaction V-value {v}");
+        };
+
+        let tx = Self {
+            nonce: nonce.into_uint()?,
+            gas_price: gas_price.into_uint()?,
+            gas: gas.into_uint()?,
+            to: to.into_address()?,
+            value: value.into_uint()?,
+            data: data.into_bytes()?,
+            chain_id,
+        };
+        let signature = transaction::decode_signature(y_parity, r, s)?;
 
-        rlp::iter(fields.iter().chain(tail.iter().flatten()))
+        Ok((tx, signature))
     }
 }
 
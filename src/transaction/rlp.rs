@@ -1,15 +1,26 @@
-//! Tiny (and inefficient) RLP encoding implementation.
+//! Tiny (and inefficient) RLP encoding and decoding implementation.
 
+use anyhow::{anyhow, bail, ensure, Context as _, Result};
+use ethaddr::Address;
 use ethnum::U256;
+use std::{
+    io::{self, Write},
+    mem,
+};
 
 /// RLP encode a list.
 pub fn list(items: &[&[u8]]) -> Vec<u8> {
+    to_vec(|buf| list_to(buf, items))
+}
+
+/// RLP encode a list of already encoded items into a writer.
+pub fn list_to(out: &mut impl Write, items: &[&[u8]]) -> io::Result<()> {
     let total_len = items.iter().map(|item| item.len()).sum();
-    let mut buf = len(total_len, 0xc0);
+    len_to(out, total_len, 0xc0)?;
     for item in items {
-        buf.extend_from_slice(item);
+        out.write_all(item)?;
     }
-    buf
+    Ok(())
 }
 
 /// RLP encodes an iterator as a list.
@@ -18,44 +29,292 @@ where
     U: AsRef<[u8]>,
     I: IntoIterator<Item = U>,
 {
-    let collected = items.into_iter().collect::<Vec<_>>();
-    let items = collected.iter().map(U::as_ref).collect::<Vec<_>>();
-    list(&items)
+    // NOTE: The items need to be collected, since the list length prefix
+    // must be encoded before the items themselves.
+    let items = items.into_iter().collect::<Vec<_>>();
+    let total_len = items.iter().map(|item| item.as_ref().len()).sum();
+    let mut buf = len(total_len, 0xc0);
+    buf.reserve_exact(total_len);
+    for item in &items {
+        buf.extend_from_slice(item.as_ref());
+    }
+    buf
 }
 
 /// RLP encode some bytes.
 pub fn bytes(bytes: &[u8]) -> Vec<u8> {
+    to_vec(|buf| bytes_to(buf, bytes))
+}
+
+/// RLP encode some bytes into a writer.
+pub fn bytes_to(out: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
     match bytes {
-        [x] if *x < 0x80 => vec![*x],
+        [x] if *x < 0x80 => out.write_all(&[*x]),
         _ => {
-            let mut buf = len(bytes.len(), 0x80);
-            buf.extend_from_slice(bytes);
-            buf
+            len_to(out, bytes.len(), 0x80)?;
+            out.write_all(bytes)
         }
     }
 }
 
 /// RLP encode a length.
 pub fn len(len: usize, offset: u8) -> Vec<u8> {
+    to_vec(|buf| len_to(buf, len, offset))
+}
+
+/// RLP encode a length into a writer.
+pub fn len_to(out: &mut impl Write, len: usize, offset: u8) -> io::Result<()> {
     if len < 56 {
-        vec![len as u8 + offset]
+        out.write_all(&[len as u8 + offset])
     } else {
         let bl_buf = len.to_be_bytes();
         let bl = {
             let start = len.leading_zeros() / 8;
             &bl_buf[start as usize..]
         };
-        let mut buf = vec![bl.len() as u8 + offset + 55];
-        buf.extend_from_slice(bl);
-        buf
+        out.write_all(&[bl.len() as u8 + offset + 55])?;
+        out.write_all(bl)
     }
 }
 
 /// RLP encode a unsigned integer. This ensures that it is shortned to its
 /// shortest little endian byte representation.
 pub fn uint(value: U256) -> Vec<u8> {
+    to_vec(|buf| uint_to(buf, value))
+}
+
+/// RLP encode an unsigned integer into a writer.
+pub fn uint_to(out: &mut impl Write, value: U256) -> io::Result<()> {
     let start = value.leading_zeros() / 8;
-    bytes(&value.to_be_bytes()[start as usize..])
+    bytes_to(out, &value.to_be_bytes()[start as usize..])
+}
+
+/// A borrowed RLP value.
+///
+/// Values compute their encoded lengths up front, so they can be written
+/// directly into a writer without first encoding their items into
+/// intermediate buffers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value<'a> {
+    Bytes(&'a [u8]),
+    Uint(U256),
+    List(Vec<Value<'a>>),
+}
+
+impl Value<'_> {
+    /// Returns the length in bytes of the RLP encoded value.
+    pub fn size(&self) -> usize {
+        match self {
+            Value::Bytes([x]) if *x < 0x80 => 1,
+            Value::Bytes(bytes) => len_size(bytes.len()) + bytes.len(),
+            Value::Uint(value) => {
+                let start = value.leading_zeros() / 8;
+                Value::Bytes(&value.to_be_bytes()[start as usize..]).size()
+            }
+            Value::List(items) => {
+                let total_len = items.iter().map(Value::size).sum();
+                len_size(total_len) + total_len
+            }
+        }
+    }
+
+    /// RLP encodes the value into a writer.
+    pub fn encode_to(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Value::Bytes(bytes) => bytes_to(out, bytes),
+            Value::Uint(value) => uint_to(out, *value),
+            Value::List(items) => {
+                // NOTE: This recomputes the sizes of nested lists at every
+                // level, which is fine for the shallow lists used by
+                // transactions.
+                len_to(out, items.iter().map(Value::size).sum(), 0xc0)?;
+                for item in items {
+                    item.encode_to(out)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// RLP encodes the value.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.size());
+        self.encode_to(&mut buf)
+            .expect("unexpected error writing to buffer");
+        buf
+    }
+}
+
+/// Returns the length in bytes of an RLP encoded length prefix.
+fn len_size(len: usize) -> usize {
+    if len < 56 {
+        1
+    } else {
+        1 + mem::size_of::<usize>() - len.leading_zeros() as usize / 8
+    }
+}
+
+/// Encodes into a new buffer.
+fn to_vec(encode: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    // Writing to an in-memory buffer does not fail.
+    encode(&mut buf).expect("unexpected error writing to buffer");
+    buf
+}
+
+/// A decoded RLP item.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Item {
+    Bytes(Vec<u8>),
+    List(Vec<Item>),
+}
+
+impl Item {
+    /// Returns the item's bytes, or an error if the item is a list.
+    pub fn into_bytes(self) -> Result<Vec<u8>> {
+        match self {
+            Item::Bytes(bytes) => Ok(bytes),
+            Item::List(_) => bail!("expected RLP bytes but got a list"),
+        }
+    }
+
+    /// Returns the item's list elements, or an error if the item is bytes.
+    pub fn into_list(self) -> Result<Vec<Item>> {
+        match self {
+            Item::Bytes(_) => bail!("expected RLP list but got bytes"),
+            Item::List(items) => Ok(items),
+        }
+    }
+
+    /// Returns the item's list elements as an array, or an error if the item
+    /// is not a list of exactly `N` elements.
+    pub fn into_array<const N: usize>(self) -> Result<[Item; N]> {
+        let items = self.into_list()?;
+        let len = items.len();
+        items
+            .try_into()
+            .map_err(|_| anyhow!("expected RLP list of {N} items but got {len}"))
+    }
+
+    /// Returns the item as an unsigned integer. This requires the integer to
+    /// be encoded in its shortest big endian byte representation.
+    pub fn into_uint(self) -> Result<U256> {
+        let bytes = self.into_bytes()?;
+        ensure!(bytes.len() <= 32, "RLP integer overflows 256 bits");
+        ensure!(
+            bytes.first() != Some(&0),
+            "non-minimal RLP integer with leading zeros"
+        );
+
+        let mut buf = [0; 32];
+        buf[32 - bytes.len()..].copy_from_slice(&bytes);
+        Ok(U256::from_be_bytes(buf))
+    }
+
+    /// Returns the item as an optional address, where empty bytes are
+    /// decoded as `None` (used to indicate contract creation transactions).
+    pub fn into_address(self) -> Result<Option<Address>> {
+        let bytes = self.into_bytes()?;
+        if bytes.is_empty() {
+            return Ok(None);
+        }
+        ensure!(
+            bytes.len() == 20,
+            "invalid RLP address length {}",
+            bytes.len()
+        );
+        Ok(Some(Address::from_slice(&bytes)))
+    }
+}
+
+/// The maximum nesting depth of RLP lists accepted when decoding.
+///
+/// Transactions only nest lists a few levels deep (for access lists), so this
+/// is plenty while preventing crafted inputs from overflowing the stack.
+const MAX_DEPTH: usize = 16;
+
+/// Decodes a single RLP item from the start of the input, returning the item
+/// along with the remaining input. This requires items to be minimally
+/// encoded, mirroring the encoding implementation.
+pub fn decode(input: &[u8]) -> Result<(Item, &[u8])> {
+    decode_nested(input, 0)
+}
+
+fn decode_nested(input: &[u8], depth: usize) -> Result<(Item, &[u8])> {
+    let (&prefix, input) = input.split_first().context("unexpected end of RLP input")?;
+    match prefix {
+        0x00..=0x7f => Ok((Item::Bytes(vec![prefix]), input)),
+        0x80..=0xbf => {
+            let (payload, rest) = payload(prefix - 0x80, input)?;
+            ensure!(
+                !matches!(payload, [x] if *x < 0x80),
+                "non-minimal RLP encoding of single byte",
+            );
+            Ok((Item::Bytes(payload.to_vec()), rest))
+        }
+        0xc0..=0xff => {
+            ensure!(depth < MAX_DEPTH, "RLP lists nested too deeply");
+            let (mut payload, rest) = payload(prefix - 0xc0, input)?;
+            let mut items = Vec::new();
+            while !payload.is_empty() {
+                let (item, remaining) = decode_nested(payload, depth + 1)?;
+                items.push(item);
+                payload = remaining;
+            }
+            Ok((Item::List(items), rest))
+        }
+    }
+}
+
+/// Decodes RLP encoded bytes, rejecting any trailing data.
+pub fn decode_bytes(input: &[u8]) -> Result<Vec<u8>> {
+    decode_exact(input)?.into_bytes()
+}
+
+/// Decodes an RLP encoded list, rejecting any trailing data.
+pub fn decode_list(input: &[u8]) -> Result<Vec<Item>> {
+    decode_exact(input)?.into_list()
+}
+
+/// Decodes an RLP encoded list of exactly `N` items, rejecting any trailing
+/// data.
+pub fn decode_array<const N: usize>(input: &[u8]) -> Result<[Item; N]> {
+    decode_exact(input)?.into_array()
+}
+
+/// Decodes a single RLP item, rejecting any trailing data.
+fn decode_exact(input: &[u8]) -> Result<Item> {
+    let (item, rest) = decode(input)?;
+    ensure!(rest.is_empty(), "unexpected trailing data after RLP item");
+    Ok(item)
+}
+
+/// Splits an RLP item's payload from the remaining input given the length
+/// value encoded in its prefix (i.e. the prefix without its offset).
+fn payload(len: u8, input: &[u8]) -> Result<(&[u8], &[u8])> {
+    let (len, input) = if len < 56 {
+        (len as usize, input)
+    } else {
+        let len_of_len = (len - 55) as usize;
+        ensure!(
+            len_of_len <= mem::size_of::<usize>(),
+            "RLP length overflows"
+        );
+        let (len_bytes, input) = input
+            .split_at_checked(len_of_len)
+            .context("truncated RLP length")?;
+        ensure!(
+            len_bytes[0] != 0,
+            "non-minimal RLP length with leading zeros"
+        );
+        let len = len_bytes
+            .iter()
+            .fold(0, |acc, &byte| (acc << 8) | byte as usize);
+        ensure!(len >= 56, "non-minimal RLP length encoding");
+        (len, input)
+    };
+
+    input.split_at_checked(len).context("truncated RLP item")
 }
 
 #[cfg(test)]
@@ -95,4 +354,186 @@ mod tests {
             b"\xb8\x38Lorem ipsum dolor sit amet, consectetur adipisicing elit"
         );
     }
+
+    #[test]
+    fn encode_value_to_writer() {
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        for (value, encoded) in [
+            (Value::Bytes(b"dog"), &b"\x83dog"[..]),
+            (
+                Value::List(vec![Value::Bytes(b"cat"), Value::Bytes(b"dog")]),
+                b"\xc8\x83cat\x83dog",
+            ),
+            (Value::Bytes(b""), &[0x80]),
+            (Value::List(vec![]), &[0xc0]),
+            (Value::Uint(U256::ZERO), &[0x80]),
+            (Value::Bytes(b"\0"), &[0x00]),
+            (Value::Uint(U256::new(15)), &[0x0f]),
+            (Value::Uint(U256::new(1024)), &[0x82, 0x04, 0x00]),
+            (
+                Value::List(vec![
+                    Value::List(vec![]),
+                    Value::List(vec![Value::List(vec![])]),
+                    Value::List(vec![
+                        Value::List(vec![]),
+                        Value::List(vec![Value::List(vec![])]),
+                    ]),
+                ]),
+                &[0xc7, 0xc0, 0xc1, 0xc0, 0xc3, 0xc0, 0xc1, 0xc0],
+            ),
+            (
+                Value::Bytes(lorem),
+                b"\xb8\x38Lorem ipsum dolor sit amet, consectetur adipisicing elit",
+            ),
+            (
+                Value::List(vec![Value::Bytes(lorem)]),
+                b"\xf8\x3a\xb8\x38Lorem ipsum dolor sit amet, consectetur adipisicing elit",
+            ),
+        ] {
+            let mut out = Vec::new();
+            value.encode_to(&mut out).unwrap();
+            assert_eq!(out, encoded);
+            assert_eq!(value.size(), encoded.len());
+            assert_eq!(value.encode(), encoded);
+        }
+        assert_eq!(Value::Bytes(&[0xff; 1024]).size(), 3 + 1024);
+    }
+
+    #[test]
+    fn iter_matches_list() {
+        let slots = [3, 7]
+            .map(|slot| bytes(&U256::new(slot).to_be_bytes()))
+            .to_vec();
+        let entries = [
+            list(&[
+                &bytes(&hex::decode("de0b295669a9fd93d5f28d9ec85e40f4cb697bae").unwrap()),
+                &iter(&slots),
+            ]),
+            list(&[
+                &bytes(&hex::decode("bb9bc244d798123fde783fcc1c72d3bb8c189413").unwrap()),
+                &iter(Vec::<Vec<u8>>::new()),
+            ]),
+        ];
+
+        for items in [&slots[..], &entries[..], &entries[..0]] {
+            assert_eq!(
+                iter(items),
+                list(&items.iter().map(Vec::as_slice).collect::<Vec<_>>()),
+            );
+        }
+        assert_eq!(
+            hex::encode(iter(&entries)),
+            "f872f85994de0b295669a9fd93d5f28d9ec85e40f4cb697baef842a000000000\
+             00000000000000000000000000000000000000000000000000000003a0000000\
+             0000000000000000000000000000000000000000000000000000000007d694bb\
+             9bc244d798123fde783fcc1c72d3bb8c189413c0",
+        );
+    }
+
+    #[test]
+    fn decode_round_trip() {
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        for (encoded, item) in [
+            (bytes(b"dog"), Item::Bytes(b"dog".to_vec())),
+            (bytes(b""), Item::Bytes(vec![])),
+            (bytes(b"\0"), Item::Bytes(vec![0])),
+            (uint(U256::new(1024)), Item::Bytes(vec![0x04, 0x00])),
+            (bytes(lorem), Item::Bytes(lorem.to_vec())),
+            (list(&[]), Item::List(vec![])),
+            (
+                list(&[&bytes(b"cat"), &bytes(b"dog")]),
+                Item::List(vec![
+                    Item::Bytes(b"cat".to_vec()),
+                    Item::Bytes(b"dog".to_vec()),
+                ]),
+            ),
+            (
+                list(&[
+                    &list(&[]),
+                    &list(&[&list(&[])]),
+                    &list(&[&list(&[]), &list(&[&list(&[])])]),
+                ]),
+                Item::List(vec![
+                    Item::List(vec![]),
+                    Item::List(vec![Item::List(vec![])]),
+                    Item::List(vec![
+                        Item::List(vec![]),
+                        Item::List(vec![Item::List(vec![])]),
+                    ]),
+                ]),
+            ),
+        ] {
+            assert_eq!(decode(&encoded).unwrap(), (item, &[][..]));
+        }
+
+        assert_eq!(decode_bytes(&bytes(b"dog")).unwrap(), b"dog");
+        assert_eq!(decode_list(&list(&[])).unwrap(), Vec::<Item>::new());
+        assert_eq!(
+            decode(b"\x83dog\x83cat").unwrap(),
+            (Item::Bytes(b"dog".to_vec()), &b"\x83cat"[..]),
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        let invalid: [&[u8]; 9] = [
+            // empty input
+            b"",
+            // trailing data
+            b"\x83dog\x00",
+            // truncated bytes
+            b"\x83do",
+            // truncated list
+            b"\xc8\x83cat\x83do",
+            // truncated length
+            b"\xb9\x04",
+            // non-minimal single byte
+            b"\x81\x01",
+            // non-minimal length
+            b"\xb8\x03dog",
+            // length with leading zeros
+            b"\xb9\x00\x38Lorem ipsum dolor sit amet, consectetur adipisicing elit",
+            // list item overflowing list payload
+            b"\xc3\x83dog",
+        ];
+        for invalid in invalid {
+            assert!(decode_exact(invalid).is_err());
+        }
+
+        assert!(decode_bytes(&list(&[])).is_err());
+        assert!(decode_list(&bytes(b"dog")).is_err());
+        assert!(decode_array::<1>(&list(&[])).is_err());
+    }
+
+    #[test]
+    fn decode_nesting_limit() {
+        let nested = |depth: usize| (0..depth).fold(list(&[]), |item, _| list(&[&item]));
+
+        assert!(decode_exact(&nested(MAX_DEPTH - 1)).is_ok());
+        assert!(decode_exact(&nested(MAX_DEPTH)).is_err());
+
+        assert_eq!(
+            decode_exact(&nested(1000)).unwrap_err().to_string(),
+            "RLP lists nested too deeply",
+        );
+    }
+
+    #[test]
+    fn decode_values() {
+        for value in [U256::ZERO, U256::new(15), U256::new(1024), U256::MAX] {
+            assert_eq!(
+                decode_exact(&uint(value)).unwrap().into_uint().unwrap(),
+                value
+            );
+        }
+        assert!(Item::Bytes(vec![0, 1]).into_uint().is_err());
+        assert!(Item::Bytes(vec![1; 33]).into_uint().is_err());
+
+        assert_eq!(Item::Bytes(vec![]).into_address().unwrap(), None);
+        assert_eq!(
+            Item::Bytes(vec![0xee; 20]).into_address().unwrap(),
+            Some(Address::from_slice(&[0xee; 20])),
+        );
+        assert!(Item::Bytes(vec![0xee; 19]).into_address().is_err());
+    }
 }
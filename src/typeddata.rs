@@ -1,8 +1,8 @@
 //! Module for hashing EIP-712 typed data.
 
 use crate::{
-    hash,
-    serialization::{self, JsonObject},
+    hash::{self, Hash32},
+    serialization::JsonObject,
 };
 use anyhow::{bail, ensure, Context as _, Result};
 use ethaddr::Address;
@@ -14,34 +14,68 @@ use serde::{
 use serde_json::Value;
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Display, Formatter, Write},
 };
 
 /// EIP-712 typed data.
 pub struct TypedData {
-    digest: [u8; 32],
-    domain_separator: [u8; 32],
-    message_hash: [u8; 32],
+    digest: Hash32,
+    domain_separator: Hash32,
+    message_hash: Hash32,
+    types: Types,
+    primary_type: String,
+    message: JsonObject,
 }
 
 impl TypedData {
     /// Returns the 32-byte message to be used for siging the typed data.
     ///
     /// This is the EIP-712 digest of the typed data.
-    pub fn signing_message(&self) -> [u8; 32] {
+    pub fn signing_message(&self) -> Hash32 {
         self.digest
     }
 
     /// Returns the 32-byte hash of the typed data domain.
-    pub fn domain_separator(&self) -> [u8; 32] {
+    pub fn domain_separator(&self) -> Hash32 {
         self.domain_separator
     }
 
     /// Returns the 32-byte hash of the typed data message.
-    pub fn message_hash(&self) -> [u8; 32] {
+    pub fn message_hash(&self) -> Hash32 {
         self.message_hash
     }
+
+    /// Returns the EIP-712 type hash for each type defined in the typed data,
+    /// sorted by type name.
+    pub fn type_hashes(&self) -> Result<Vec<(&str, Hash32)>> {
+        let mut kinds = self.types.0.keys().map(String::as_str).collect::<Vec<_>>();
+        kinds.sort_unstable();
+        kinds
+            .into_iter()
+            .map(|kind| Ok((kind, self.types.type_hash(kind)?)))
+            .collect()
+    }
+
+    /// Returns the EIP-712 `hashStruct` of the first value of the specified
+    /// struct type in the typed data message, searching its members
+    /// depth-first. For the primary type, this is the message hash.
+    pub fn struct_hash_of(&self, kind: &str) -> Result<Hash32> {
+        self.types.type_definition(kind)?;
+        let data = if kind == self.primary_type {
+            &self.message
+        } else {
+            self.types
+                .find_struct(&self.primary_type, &self.message, kind)
+                .with_context(|| format!("typed data message has no {kind} value"))?
+        };
+        self.types.struct_hash(kind, data.clone())
+    }
+
+    /// Returns the EIP-712 type definitions of the typed data.
+    pub fn types(&self) -> &Types {
+        &self.types
+    }
 }
 
 impl<'de> Deserialize<'de> for TypedData {
@@ -49,9 +83,11 @@ impl<'de> Deserialize<'de> for TypedData {
     where
         D: Deserializer<'de>,
     {
+        // NOTE: Include the full error chain, so that errors for nested values
+        // also mention the offending property.
         TypedDataBlob::deserialize(deserializer)?
             .compute()
-            .map_err(de::Error::custom)
+            .map_err(|err| de::Error::custom(format!("{err:#}")))
     }
 }
 
@@ -66,6 +102,8 @@ struct TypedDataBlob {
 
 impl TypedDataBlob {
     fn compute(self) -> Result<TypedData> {
+        self.types.verify_references(&self.primary_type)?;
+        self.types.verify_not_recursive()?;
         self.verify_domain_type()?;
 
         let TypedDataBlob {
@@ -75,18 +113,17 @@ impl TypedDataBlob {
             message,
         } = self;
         let domain_separator = types.struct_hash("EIP712Domain", domain)?;
-        let message_hash = types.struct_hash(&primary_type, message)?;
+        let message_hash = types.struct_hash(&primary_type, message.clone())?;
 
-        let mut buffer = [0; 66];
-        buffer[0..2].copy_from_slice(b"\x19\x01");
-        buffer[2..34].copy_from_slice(&domain_separator);
-        buffer[34..66].copy_from_slice(&message_hash);
-        let digest = hash::keccak256(buffer);
+        let digest = hash::keccak256_parts(&[b"\x19\x01", &*domain_separator, &*message_hash]);
 
         Ok(TypedData {
             digest,
             domain_separator,
             message_hash,
+            types,
+            primary_type,
+            message,
         })
     }
 
@@ -122,23 +159,35 @@ impl TypedDataBlob {
     }
 }
 
+/// EIP-712 type definitions, mapping struct type names to their members.
+///
+/// This exposes the intermediate steps of EIP-712 hashing, which is useful for
+/// comparing against values computed by contracts.
 #[derive(Deserialize)]
 #[serde(transparent)]
-struct Types(HashMap<String, Vec<Member>>);
+pub struct Types(HashMap<String, Vec<Member>>);
 
 impl Types {
-    fn struct_hash(&self, kind: &str, mut data: JsonObject) -> Result<[u8; 32]> {
+    /// Returns the EIP-712 `hashStruct` of the specified struct type and data.
+    pub fn struct_hash(&self, kind: &str, data: JsonObject) -> Result<Hash32> {
+        Ok(hash::keccak256(self.encode_data(kind, data)?))
+    }
+
+    /// Returns the EIP-712 encoding of the specified struct type and data,
+    /// including the leading type hash. This is the same as Solidity's
+    /// `abi.encode(typeHash, ...)` of the encoded members.
+    pub fn encode_data(&self, kind: &str, mut data: JsonObject) -> Result<Vec<u8>> {
         let type_definition = self.type_definition(kind)?;
         let mut buffer = vec![0_u8; 32 * (1 + type_definition.members.len())];
-        buffer[0..32].copy_from_slice(&self.type_hash(kind)?);
+        buffer[0..32].copy_from_slice(&*self.type_hash(kind)?);
         for (i, member) in type_definition.members.iter().enumerate() {
+            let value = data
+                .remove(&member.name)
+                .with_context(|| format!("{kind} value missing property {}", member.name))?;
             buffer[(i + 1) * 32..][..32].copy_from_slice(
-                &self.encode_value(
-                    &member.kind,
-                    data.remove(&member.name).with_context(|| {
-                        format!("{kind} value missing property {}", member.name)
-                    })?,
-                )?,
+                &self
+                    .encode_value(&member.kind, value)
+                    .with_context(|| format!("invalid {kind} property {}", member.name))?,
             );
         }
 
@@ -147,18 +196,21 @@ impl Types {
             "additional unspecified {kind} properties: {}",
             data.keys().cloned().collect::<Vec<_>>().join(", "),
         );
-        Ok(hash::keccak256(&buffer))
+        Ok(buffer)
     }
 
-    fn encode_type(&self, kind: &str) -> Result<String> {
+    /// Returns the EIP-712 `encodeType` of the specified struct type, that is
+    /// its definition followed by the definitions of all referenced struct
+    /// types sorted by name.
+    pub fn encode_type(&self, kind: &str) -> Result<String> {
         let type_definition = self.type_definition(kind)?;
         let mut sub_types = BTreeMap::new();
 
         let mut unresolved_sub_types = type_definition.struct_references().collect::<Vec<_>>();
-        while let Some(sub_type_name) = unresolved_sub_types
-            .pop()
-            .filter(|name| !sub_types.contains_key(name))
-        {
+        while let Some(sub_type_name) = unresolved_sub_types.pop() {
+            if sub_type_name == kind || sub_types.contains_key(sub_type_name) {
+                continue;
+            }
             let sub_type = self.type_definition(sub_type_name)?;
             unresolved_sub_types.extend(sub_type.struct_references());
             sub_types.insert(sub_type_name, sub_type);
@@ -172,11 +224,116 @@ impl Types {
         Ok(buffer)
     }
 
-    fn type_hash(&self, kind: &str) -> Result<[u8; 32]> {
+    /// Returns the EIP-712 `typeHash` of the specified struct type.
+    pub fn type_hash(&self, kind: &str) -> Result<Hash32> {
         let encoded_type = self.encode_type(kind)?;
         Ok(hash::keccak256(encoded_type))
     }
 
+    /// Verifies that the primary type and all struct types referenced by
+    /// members of any type definition are defined. This allows reporting all
+    /// missing type definitions at once, instead of failing on the first one
+    /// that is encountered during encoding.
+    fn verify_references(&self, primary_type: &str) -> Result<()> {
+        let undefined = self
+            .0
+            .values()
+            .flatten()
+            .filter_map(|member| member.kind.struct_reference())
+            .chain([primary_type])
+            .filter(|kind| !self.0.contains_key(*kind))
+            .collect::<BTreeSet<_>>();
+        ensure!(
+            undefined.is_empty(),
+            "undefined EIP-712 types: {}",
+            undefined.into_iter().collect::<Vec<_>>().join(", "),
+        );
+        Ok(())
+    }
+
+    /// Verifies that no struct type recursively contains itself other than
+    /// through dynamically sized arrays. Values for such types can not exist,
+    /// since they would need to be infinitely nested.
+    fn verify_not_recursive(&self) -> Result<()> {
+        fn visit<'a>(
+            types: &'a Types,
+            kind: &'a str,
+            path: &mut Vec<&'a str>,
+            checked: &mut HashSet<&'a str>,
+        ) -> Result<()> {
+            ensure!(
+                !path.contains(&kind),
+                "recursive EIP-712 type definition involving {kind}",
+            );
+            // NOTE: Types that were already fully visited are known not to be
+            // recursive, so don't walk them again. Without this, type graphs
+            // that reference the same types along many paths take exponential
+            // time to check.
+            if checked.contains(kind) {
+                return Ok(());
+            }
+            // NOTE: Missing type definitions are reported when encoding.
+            let Some(members) = types.0.get(kind) else {
+                return Ok(());
+            };
+
+            path.push(kind);
+            for member in members {
+                if let Some(reference) = member.kind.required_struct_reference() {
+                    visit(types, reference, path, checked)?;
+                }
+            }
+            path.pop();
+            checked.insert(kind);
+            Ok(())
+        }
+
+        // NOTE: Visit types in a deterministic order, so that the reported
+        // type is stable for a given set of type definitions.
+        let kinds = self.0.keys().map(String::as_str).collect::<BTreeSet<_>>();
+        let mut checked = HashSet::new();
+        for kind in kinds {
+            visit(self, kind, &mut Vec::new(), &mut checked)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the first value of the `target` struct type nested in the
+    /// specified struct data, searching members depth-first.
+    fn find_struct<'a>(
+        &self,
+        kind: &str,
+        data: &'a JsonObject,
+        target: &str,
+    ) -> Option<&'a JsonObject> {
+        self.0.get(kind)?.iter().find_map(|member| {
+            self.find_struct_value(&member.kind, data.get(&member.name)?, target)
+        })
+    }
+
+    fn find_struct_value<'a>(
+        &self,
+        kind: &MemberKind,
+        value: &'a Value,
+        target: &str,
+    ) -> Option<&'a JsonObject> {
+        match kind {
+            MemberKind::Struct(name) => {
+                let data = value.as_object()?;
+                if name == target {
+                    Some(data)
+                } else {
+                    self.find_struct(name, data, target)
+                }
+            }
+            MemberKind::Array(inner, _) => value
+                .as_array()?
+                .iter()
+                .find_map(|value| self.find_struct_value(inner, value, target)),
+            _ => None,
+        }
+    }
+
     fn type_definition<'a>(&'a self, kind: &'a str) -> Result<TypeDefinition<'a>> {
         let members = self
             .0
@@ -189,7 +346,7 @@ impl Types {
     fn encode_value(&self, kind: &MemberKind, value: Value) -> Result<[u8; 32]> {
         Ok(match kind {
             MemberKind::Bytes(n) => {
-                let bytes = serialization::bytes::deserialize(value)?;
+                let bytes = decode_bytes(value)?;
                 match n {
                     Some(n) => {
                         ensure!(
@@ -204,7 +361,7 @@ impl Types {
                             .copy_from_slice(&bytes);
                         buffer
                     }
-                    None => hash::keccak256(&bytes),
+                    None => *hash::keccak256(&bytes),
                 }
             }
             MemberKind::Uint(n) => {
@@ -234,13 +391,13 @@ impl Types {
                 buffer[12..].copy_from_slice(&*address);
                 buffer
             }
-            MemberKind::String => hash::keccak256(&*Cow::<str>::deserialize(value)?),
+            MemberKind::String => *hash::keccak256(&*Cow::<str>::deserialize(value)?),
             MemberKind::Struct(inner) => {
                 let value = match value {
                     Value::Object(value) => value,
                     value => bail!("expected JSON object but got '{value}'"),
                 };
-                self.struct_hash(inner, value)?
+                *self.struct_hash(inner, value)?
             }
             MemberKind::Array(inner, size) => {
                 let value = match value {
@@ -259,12 +416,22 @@ impl Types {
                 for (i, element) in value.into_iter().enumerate() {
                     buffer[(i * 32)..][..32].copy_from_slice(&self.encode_value(inner, element)?);
                 }
-                hash::keccak256(&buffer)
+                *hash::keccak256(&buffer)
             }
         })
     }
 }
 
+/// Decodes a `0x`-prefixed hex string value for a `bytes` or `bytesN` member.
+fn decode_bytes(value: Value) -> Result<Vec<u8>> {
+    let value = Cow::<str>::deserialize(value)?;
+    let hex = value
+        .strip_prefix("0x")
+        .context("missing '0x' prefix for bytes value")?;
+    ensure!(hex.len() % 2 == 0, "odd-length hex string for bytes value");
+    hex::decode(hex).context("invalid hex string for bytes value")
+}
+
 struct TypeDefinition<'a> {
     kind: &'a str,
     members: &'a [Member],
@@ -281,7 +448,7 @@ impl<'a> TypeDefinition<'a> {
 impl Display for TypeDefinition<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}(", self.kind)?;
-        if let Some(first_member) = self.members.get(0) {
+        if let Some(first_member) = self.members.first() {
             write!(f, "{first_member}")?;
         }
         for member in self.members.get(1..).into_iter().flatten() {
@@ -358,6 +525,17 @@ impl MemberKind {
             _ => None,
         }
     }
+
+    /// Returns the struct type that is contained in every value of this kind.
+    /// This excludes dynamically sized and empty arrays, which may not contain
+    /// any struct values.
+    fn required_struct_reference(&self) -> Option<&str> {
+        match self {
+            MemberKind::Struct(name) => Some(name),
+            MemberKind::Array(inner, Some(n)) if *n > 0 => inner.required_struct_reference(),
+            _ => None,
+        }
+    }
 }
 
 impl Display for MemberKind {
@@ -440,6 +618,134 @@ mod tests {
             typed_data.signing_message(),
             hex!("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"),
         );
+
+        assert_eq!(
+            typed_data.struct_hash_of("Mail").unwrap(),
+            typed_data.message_hash(),
+        );
+        assert_eq!(
+            typed_data.struct_hash_of("Person").unwrap(),
+            hex!("fc71e5fa27ff56c350aa531bc129ebdf613b772b6604664f5d8dbe21b85eb0c8"),
+        );
+        assert!(typed_data.struct_hash_of("EIP712Domain").is_err());
+        assert!(typed_data.struct_hash_of("Missing").is_err());
+    }
+
+    #[test]
+    fn type_hashes() {
+        let typed_data = serde_json::from_value::<TypedData>(json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {
+                    "name": "Cow",
+                    "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                },
+                "to": {
+                    "name": "Bob",
+                    "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+                },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            typed_data.type_hashes().unwrap(),
+            [
+                (
+                    "EIP712Domain",
+                    Hash32(hex!(
+                        "8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f"
+                    )),
+                ),
+                (
+                    "Mail",
+                    Hash32(hex!(
+                        "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+                    )),
+                ),
+                (
+                    "Person",
+                    Hash32(hex!(
+                        "b9d8c78acf9b987311de6c7b45bb6a9c8e1bf361fa7fd3467a2163f994c79500"
+                    )),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn public_struct_hashing() {
+        let types = serde_json::from_value::<Types>(json!({
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        }))
+        .unwrap();
+        let person = |name: &str, wallet: &str| {
+            json!({ "name": name, "wallet": wallet })
+                .as_object()
+                .unwrap()
+                .clone()
+        };
+        let cow = person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+
+        assert_eq!(
+            types.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+        );
+        assert_eq!(
+            types.type_hash("Mail").unwrap(),
+            hex!("a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"),
+        );
+
+        let encoded = types.encode_data("Person", cow.clone()).unwrap();
+        assert_eq!(encoded.len(), 96);
+        assert_eq!(encoded[..32], *types.type_hash("Person").unwrap());
+        assert_eq!(
+            types.struct_hash("Person", cow.clone()).unwrap(),
+            hex!("fc71e5fa27ff56c350aa531bc129ebdf613b772b6604664f5d8dbe21b85eb0c8"),
+        );
+
+        let mail = json!({
+            "from": cow,
+            "to": person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            "contents": "Hello, Bob!"
+        });
+        assert_eq!(
+            types
+                .struct_hash("Mail", mail.as_object().unwrap().clone())
+                .unwrap(),
+            hex!("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"),
+        );
     }
 
     #[test]
@@ -704,6 +1010,212 @@ mod tests {
         );
     }
 
+    #[test]
+    fn domain_salt() {
+        let domain_separator = |types: Value, domain: Value| {
+            serde_json::from_value::<TypedData>(json!({
+                "types": {
+                    "EIP712Domain": types,
+                    "Empty": [],
+                },
+                "primaryType": "Empty",
+                "domain": domain,
+                "message": {},
+            }))
+            .map(|typed_data| typed_data.domain_separator())
+        };
+        let salt = "0xf2d857f4a3edcb9b78b4d503bfe733db1e3f6cdc2b7971ee739626c97e86a558";
+
+        assert_eq!(
+            domain_separator(
+                json!([
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                    { "name": "salt", "type": "bytes32" }
+                ]),
+                json!({
+                    "name": "Ether Mail",
+                    "version": "1",
+                    "chainId": 1,
+                    "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+                    "salt": salt,
+                }),
+            )
+            .unwrap(),
+            hex!("bae4f6f7b9bfdfda060692099b0e1ccecd25d62b7c92cc9f3b907f33178b81e3"),
+        );
+
+        let types = json!([
+            { "name": "name", "type": "string" },
+            { "name": "chainId", "type": "uint256" },
+            { "name": "salt", "type": "bytes32" }
+        ]);
+        assert_eq!(
+            domain_separator(
+                types.clone(),
+                json!({
+                    "name": "Ether Mail",
+                    "chainId": 1,
+                    "salt": salt,
+                }),
+            )
+            .unwrap(),
+            hex!("0d19874534633322709d5a10e76ee747b86cdbdb605073e336df6fe6f292002c"),
+        );
+        assert!(domain_separator(
+            types,
+            json!({
+                "name": "Ether Mail",
+                "chainId": 1,
+                "salt": "0x0102",
+            }),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn undefined_types() {
+        let err = serde_json::from_value::<TypedData>(json!({
+            "types": {
+                "EIP712Domain": [{ "name": "name", "type": "string" }],
+                "Order": [
+                    { "name": "maker", "type": "Person" },
+                    { "name": "items", "type": "Item[]" },
+                    { "name": "nested", "type": "Nested" }
+                ],
+                "Nested": [{ "name": "fees", "type": "Fee[2][]" }]
+            },
+            "primaryType": "Orders",
+            "domain": { "name": "Test" },
+            "message": {}
+        }))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "undefined EIP-712 types: Fee, Item, Orders, Person",
+        );
+    }
+
+    #[test]
+    fn invalid_bytes_value() {
+        let typed_data = |data: &str| {
+            serde_json::from_value::<TypedData>(json!({
+                "types": {
+                    "EIP712Domain": [{ "name": "name", "type": "string" }],
+                    "Call": [{ "name": "data", "type": "bytes" }]
+                },
+                "primaryType": "Call",
+                "domain": { "name": "Test" },
+                "message": { "data": data }
+            }))
+        };
+
+        assert_eq!(
+            typed_data("0x123").unwrap_err().to_string(),
+            "invalid Call property data: odd-length hex string for bytes value",
+        );
+        assert_eq!(
+            typed_data("0x12zz").unwrap_err().to_string(),
+            "invalid Call property data: invalid hex string for bytes value: \
+             Invalid character 'z' at position 2",
+        );
+        assert_eq!(
+            typed_data("1234").unwrap_err().to_string(),
+            "invalid Call property data: missing '0x' prefix for bytes value",
+        );
+        assert!(typed_data("0x1234").is_ok());
+    }
+
+    #[test]
+    fn recursive_types() {
+        let types = serde_json::from_value::<Types>(json!({
+            "A": [{ "name": "b", "type": "B" }],
+            "B": [{ "name": "a", "type": "A" }],
+        }))
+        .unwrap();
+        assert_eq!(
+            types.verify_not_recursive().unwrap_err().to_string(),
+            "recursive EIP-712 type definition involving A",
+        );
+        assert!(types
+            .struct_hash("A", json!({ "b": {} }).as_object().unwrap().clone())
+            .is_err());
+
+        let types = serde_json::from_value::<Types>(json!({
+            "A": [{ "name": "bs", "type": "B[]" }],
+            "B": [{ "name": "a", "type": "A[1]" }, { "name": "c", "type": "C" }],
+            "C": [{ "name": "value", "type": "uint256" }],
+            "Node": [{ "name": "children", "type": "Node[]" }],
+            "Pair": [{ "name": "pair", "type": "Pair[2]" }],
+        }))
+        .unwrap();
+        assert_eq!(
+            types.encode_type("A").unwrap(),
+            "A(B[] bs)B(A[1] a,C c)C(uint256 value)",
+        );
+        assert_eq!(
+            types.encode_type("B").unwrap(),
+            "B(A[1] a,C c)A(B[] bs)C(uint256 value)",
+        );
+        assert_eq!(types.encode_type("Node").unwrap(), "Node(Node[] children)");
+        assert_eq!(
+            types.verify_not_recursive().unwrap_err().to_string(),
+            "recursive EIP-712 type definition involving Pair",
+        );
+
+        let typed_data = serde_json::from_value::<TypedData>(json!({
+            "types": {
+                "EIP712Domain": [{ "name": "name", "type": "string" }],
+                "A": [{ "name": "b", "type": "B" }],
+                "B": [{ "name": "a", "type": "A" }],
+            },
+            "primaryType": "A",
+            "domain": { "name": "recursive" },
+            "message": { "b": { "a": {} } },
+        }));
+        assert!(typed_data
+            .unwrap_err()
+            .to_string()
+            .contains("recursive EIP-712 type definition involving A"));
+    }
+
+    #[test]
+    fn wide_and_deep_type_graph() {
+        // NOTE: Each type references the next one through several members, so
+        // there are 8^64 distinct paths from the first type to the last.
+        const DEPTH: usize = 64;
+        const WIDTH: usize = 8;
+
+        let types = Types(
+            (0..DEPTH)
+                .map(|i| {
+                    let members = if i + 1 < DEPTH {
+                        (0..WIDTH)
+                            .map(|j| Member {
+                                name: format!("m{j}"),
+                                kind: MemberKind::Struct(format!("T{}", i + 1)),
+                            })
+                            .collect()
+                    } else {
+                        vec![Member {
+                            name: "value".to_string(),
+                            kind: MemberKind::Uint(256),
+                        }]
+                    };
+                    (format!("T{i}"), members)
+                })
+                .collect(),
+        );
+
+        types.verify_not_recursive().unwrap();
+        assert!(types
+            .encode_type("T0")
+            .unwrap()
+            .contains("T63(uint256 value)"));
+    }
+
     #[test]
     fn encode_value_error() {
         let types = serde_json::from_str::<Types>(
@@ -0,0 +1,177 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+#[test]
+fn prints_account_address() {
+    let address = Hdwallet::run("address", &[]);
+    assert_eq!(address, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+}
+
+#[test]
+fn prints_multiple_account_addresses() {
+    let addresses = Hdwallet::run("address", &["--count", "2"]);
+    assert_eq!(
+        addresses.lines().collect::<Vec<_>>(),
+        [
+            "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1",
+            "0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0",
+        ],
+    );
+
+    let addresses = Hdwallet::run("address", &["--count", "3", "--start", "1"]);
+    let expected = (1..4)
+        .map(|index| Hdwallet::run("address", &["--account-index", &index.to_string()]))
+        .collect::<Vec<_>>();
+    assert_eq!(addresses.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn prints_address_for_key_material() {
+    let address = Hdwallet::new(
+        "address",
+        &[
+            "--private-key",
+            "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d",
+        ],
+    )
+    .env_remove("MNEMONIC")
+    .execute()
+    .unwrap();
+    assert_eq!(address, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+
+    let address = Hdwallet::new(
+        "address",
+        &[
+            "--public-key",
+            "0x03e68acfc0253a10620dff706b0a1b1f1f5833ea3beb3bde2250d5f271f3563606",
+        ],
+    )
+    .env_remove("MNEMONIC")
+    .execute()
+    .unwrap();
+    assert_eq!(address, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+
+    for (key, value) in [
+        (
+            "--private-key",
+            "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d",
+        ),
+        (
+            "--public-key",
+            "0x03e68acfc0253a10620dff706b0a1b1f1f5833ea3beb3bde2250d5f271f3563606",
+        ),
+    ] {
+        for derivation in [
+            &["--coin-type", "1"][..],
+            &["--account", "1"],
+            &["--change", "1"],
+            &["--prompt-password"],
+        ] {
+            let args = [&[key, value][..], derivation].concat();
+            assert!(Hdwallet::new("address", &args).execute().is_err());
+        }
+
+        // Account options from the environment are ignored.
+        let address = Hdwallet::new("address", &[key, value])
+            .env("PASSWORD", "secret")
+            .env("HD_PATH", "m/44'/60'/0'/0/1")
+            .execute()
+            .unwrap();
+        assert_eq!(address, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+    }
+}
+
+#[test]
+fn prints_chain_specific_checksum() {
+    let addresses = Hdwallet::run("address", &["--count", "2", "--chain-id", "30"]);
+    assert_eq!(
+        addresses.lines().collect::<Vec<_>>(),
+        [
+            "0x90F8BF6A479F320EaD074411A4b0e7944eA8C9C1",
+            "0xffcF8fdee72aC11B5c542428b35eEf5769C409f0",
+        ],
+    );
+}
+
+#[test]
+fn prints_hd_paths_with_addresses() {
+    let addresses = Hdwallet::run("address", &["--count", "2", "--show-path"]);
+    assert_eq!(
+        addresses.lines().collect::<Vec<_>>(),
+        [
+            "m/44'/60'/0'/0/0  0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1",
+            "m/44'/60'/0'/0/1  0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0",
+        ],
+    );
+}
+
+#[test]
+fn composes_bip44_paths() {
+    let address = Hdwallet::run(
+        "address",
+        &["--account", "1", "--change", "1", "--account-index", "2"],
+    );
+    assert_eq!(
+        address,
+        Hdwallet::run("address", &["--hd-path", "m/44'/60'/1'/1/2"]),
+    );
+
+    let addresses = Hdwallet::run(
+        "address",
+        &["--coin-type", "1", "--count", "2", "--show-path"],
+    );
+    assert_eq!(
+        addresses
+            .lines()
+            .map(|line| line.split_whitespace().next().unwrap())
+            .collect::<Vec<_>>(),
+        ["m/44'/1'/0'/0/0", "m/44'/1'/0'/0/1"],
+    );
+    assert_eq!(
+        addresses.lines().next().unwrap().split_whitespace().nth(1),
+        Some(Hdwallet::run("address", &["--hd-path", "m/44'/1'/0'/0/0"]).as_str()),
+    );
+
+    assert!(Hdwallet::new(
+        "address",
+        &["--hd-path", "m/44'/60'/0'/0/0", "--change", "1"]
+    )
+    .execute()
+    .is_err());
+}
+
+#[test]
+fn prints_raw_addresses() {
+    let address = Hdwallet::run("address", &["--raw"]);
+    assert_eq!(address, "0x90f8bf6a479f320ead074411a4b0e7944ea8c9c1");
+
+    let address = Hdwallet::run("address", &["--no-prefix"]);
+    assert_eq!(address, "90f8bf6a479f320ead074411a4b0e7944ea8c9c1");
+}
+
+#[test]
+fn prints_contract_addresses() {
+    let sender = "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1";
+    let address = Hdwallet::run("address", &["contract", "--sender", sender, "--nonce", "0"]);
+    assert_eq!(address, "0xe78A0F7E598Cc8b0Bb87894B0F60dD2a88d6a8Ab");
+
+    let address = Hdwallet::run(
+        "address",
+        &[
+            "contract",
+            "--sender",
+            "0x0000000000000000000000000000000000000000",
+            "--create2",
+            "--salt",
+            "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "--init-code-hash",
+            "0xbc36789e7a1e281436464229828f817d6612f7b477d66591ff96a9e064bcc98a",
+        ],
+    );
+    assert_eq!(address, "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38");
+
+    assert!(Hdwallet::new("address", &["contract", "--sender", sender])
+        .execute()
+        .is_err());
+}
@@ -0,0 +1,28 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+/// The account extended public key for the `m/44'/60'/0'` HD path of the
+/// Ganache deterministic mnemonic.
+const XPUB: &str = "xpub6DNro2eEZk9SreVWArMUamKzpa4oV7bJ9T8ffVKxbDPxrhToccxwCLg97v2ct8tk8TNsUEUj6XCUzQmb6LGzZTANdZDPC2KqLk4o3EnPfFi";
+
+#[test]
+fn derives_addresses_from_xpub() {
+    let addresses = Hdwallet::run("addresses", &["--xpub", XPUB, "--count", "5"]);
+    let expected = Hdwallet::run("address", &["--count", "5"]);
+    assert_eq!(addresses, expected);
+
+    let addresses = Hdwallet::run(
+        "addresses",
+        &["--xpub", XPUB, "--count", "3", "--start", "2"],
+    );
+    let expected = Hdwallet::run("address", &["--count", "3", "--start", "2"]);
+    assert_eq!(addresses, expected);
+}
+
+#[test]
+fn rejects_extended_private_keys() {
+    let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+    let result = Hdwallet::new("addresses", &["--xpub", xprv]).execute();
+    assert!(result.is_err());
+}
@@ -0,0 +1,66 @@
+mod util;
+
+use crate::util::Hdwallet;
+use std::{env, fs, process};
+
+#[test]
+fn loads_account_options_from_config_file() {
+    let path = env::temp_dir().join(format!("hdwallet-config-{}", process::id()));
+    fs::write(
+        &path,
+        "# BIP-0039 test vector wallet\n\
+         MNEMONIC=\"abandon abandon abandon abandon abandon abandon \
+                    abandon abandon abandon abandon abandon about\"\n\
+         ACCOUNT_INDEX=1\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+    }
+
+    let path = path.to_str().unwrap();
+    let address = |args: &[&str]| {
+        Hdwallet::new("address", &[&["--config", path][..], args].concat())
+            .env_remove("MNEMONIC")
+            .execute()
+            .unwrap()
+    };
+    let configured = address(&[]);
+    let overridden = address(&["--account-index", "0"]);
+    let environment = Hdwallet::run("address", &["--config", path]);
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(configured, "0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0");
+    assert_eq!(overridden, "0x9858EfFD232B4033E47d90003D41EC34EcaEda94");
+    // Environment variables take precedence over the configuration file.
+    assert_eq!(environment, "0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0");
+}
+
+#[test]
+fn configured_hd_path_is_overridden_by_account_options() {
+    let path = env::temp_dir().join(format!("hdwallet-config-hd-path-{}", process::id()));
+    fs::write(&path, "HD_PATH=m/44'/60'/0'/0/1\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+    }
+
+    let path = path.to_str().unwrap();
+    let address =
+        |args: &[&str]| Hdwallet::run("address", &[&["--config", path][..], args].concat());
+    let configured = address(&[]);
+    let overridden = address(&["--account-index", "0"]);
+    let multiple = address(&["--count", "2"]);
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(configured, "0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0");
+    assert_eq!(overridden, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+    assert_eq!(
+        multiple,
+        "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1\n\
+         0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0",
+    );
+}
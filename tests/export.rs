@@ -0,0 +1,44 @@
+mod util;
+
+use crate::util::Hdwallet;
+use serde_json::{json, Value};
+
+#[test]
+fn exports_private_key() {
+    let key = Hdwallet::run("export", &[]);
+    assert_eq!(
+        key,
+        "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d",
+    );
+}
+
+#[test]
+fn exports_hardhat_accounts() {
+    let accounts = Hdwallet::run("export", &["--format", "hardhat", "--count", "3"]);
+    assert_eq!(
+        serde_json::from_str::<Value>(&accounts).unwrap(),
+        json!([
+            {
+                "privateKey": "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d",
+                "balance": "10000000000000000000000",
+            },
+            {
+                "privateKey": "0x6cbed15c793ce57650b9877cf6fa156fbef513c4e6134f022a85b1ffdd59b2a1",
+                "balance": "10000000000000000000000",
+            },
+            {
+                "privateKey": "0x6370fd033278c143179d81c5526140625662b8daa446c22ee2d73db3707e620c",
+                "balance": "10000000000000000000000",
+            },
+        ]),
+    );
+}
+
+#[test]
+fn errors_on_multiple_accounts_with_hd_path() {
+    assert!(
+        Hdwallet::new("export", &["--count", "2", "--hd-path", "m/44'/60'/0'/0/0"],)
+            .execute()
+            .is_err()
+    );
+}
@@ -0,0 +1,119 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+const TYPED_DATA: &str = r#"{
+    "types": {
+        "EIP712Domain": [
+            { "name": "name", "type": "string" },
+            { "name": "version", "type": "string" },
+            { "name": "chainId", "type": "uint256" },
+            { "name": "verifyingContract", "type": "address" }
+        ],
+        "Person": [
+            { "name": "name", "type": "string" },
+            { "name": "wallet", "type": "address" }
+        ],
+        "Mail": [
+            { "name": "from", "type": "Person" },
+            { "name": "to", "type": "Person" },
+            { "name": "contents", "type": "string" }
+        ]
+    },
+    "primaryType": "Mail",
+    "domain": {
+        "name": "Ether Mail",
+        "version": "1",
+        "chainId": 1,
+        "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+    },
+    "message": {
+        "from": {
+            "name": "Cow",
+            "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+        },
+        "to": {
+            "name": "Bob",
+            "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+        },
+        "contents": "Hello, Bob!"
+    }
+}"#;
+
+#[test]
+fn prints_type_hashes() {
+    let type_hashes = Hdwallet::new("hash", &["typeddata", "--type-hashes", "-"])
+        .stdin(TYPED_DATA)
+        .execute()
+        .unwrap();
+    assert_eq!(
+        type_hashes.lines().collect::<Vec<_>>(),
+        [
+            "EIP712Domain: 0x8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f",
+            "Mail: 0xa0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2",
+            "Person: 0xb9d8c78acf9b987311de6c7b45bb6a9c8e1bf361fa7fd3467a2163f994c79500",
+        ],
+    );
+}
+
+#[test]
+fn prints_eip712_hashes() {
+    let hash = |args: &[&str]| {
+        let args = ["typeddata"]
+            .into_iter()
+            .chain(args.iter().copied())
+            .chain(["-"])
+            .collect::<Vec<_>>();
+        Hdwallet::new("hash", &args).stdin(TYPED_DATA).execute()
+    };
+
+    assert_eq!(
+        hash(&[]).unwrap(),
+        "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+    );
+    assert_eq!(
+        hash(&["--message-hash"]).unwrap(),
+        "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e",
+    );
+    assert_eq!(
+        hash(&["--domain-separator"]).unwrap(),
+        "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+    );
+    assert!(hash(&["--domain-separator", "--message-hash"]).is_err());
+
+    assert_eq!(
+        hash(&["--type", "Mail"]).unwrap(),
+        "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e",
+    );
+    assert_eq!(
+        hash(&["--type", "Person"]).unwrap(),
+        "0xfc71e5fa27ff56c350aa531bc129ebdf613b772b6604664f5d8dbe21b85eb0c8",
+    );
+    assert!(hash(&["--type", "EIP712Domain"]).is_err());
+    assert!(hash(&["--type", "Missing"]).is_err());
+}
+
+#[test]
+fn accepts_json5_input() {
+    let typed_data = TYPED_DATA
+        .replace(
+            r#""primaryType": "Mail","#,
+            "// The primary type.\n\"primaryType\": \"Mail\", /* Mail */",
+        )
+        .replace(
+            r#""contents": "Hello, Bob!""#,
+            r#""contents": "Hello, Bob!","#,
+        );
+
+    assert!(Hdwallet::new("hash", &["typeddata", "-"])
+        .stdin(typed_data.as_str())
+        .execute()
+        .is_err());
+    assert_eq!(
+        Hdwallet::new("hash", &["typeddata", "--json5", "-"])
+            .stdin(typed_data.as_str())
+            .execute()
+            .unwrap(),
+        "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+    );
+}
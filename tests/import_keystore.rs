@@ -0,0 +1,45 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+const KEYSTORE: &str = r#"{
+    "version": 3,
+    "id": "33333333-3333-4333-b333-333333333333",
+    "address": "90f8bf6a479f320ead074411a4b0e7944ea8c9c1",
+    "crypto": {
+        "cipher": "aes-128-ctr",
+        "cipherparams": {
+            "iv": "22222222222222222222222222222222"
+        },
+        "ciphertext": "399da4322c5ee60be0b4de859c5ddac4dd11292a065e87eb3c8a5d643d8732e6",
+        "kdf": "scrypt",
+        "kdfparams": {
+            "dklen": 32,
+            "n": 1024,
+            "r": 8,
+            "p": 1,
+            "salt": "1111111111111111111111111111111111111111111111111111111111111111"
+        },
+        "mac": "6e91087c374b940031538a17c31f8dba14e59e96303e53e9f75febce49554612"
+    }
+}"#;
+
+#[test]
+fn imports_keystore() {
+    let address = Hdwallet::new("import-keystore", &["-"])
+        .env("KEYSTORE_PASSWORD", "hdwallet")
+        .stdin(KEYSTORE)
+        .execute()
+        .unwrap();
+    assert_eq!(address, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+}
+
+#[test]
+fn errors_on_wrong_keystore_password() {
+    let err = Hdwallet::new("import-keystore", &["-"])
+        .env("KEYSTORE_PASSWORD", "wrong")
+        .stdin(KEYSTORE)
+        .execute()
+        .unwrap_err();
+    assert!(err.contains("password is incorrect"));
+}
@@ -0,0 +1,110 @@
+mod util;
+
+use crate::util::Hdwallet;
+use serde_json::{json, Value};
+
+fn run_json(subcommand: &str, args: &[&str]) -> Value {
+    serde_json::from_str(&Hdwallet::run(subcommand, args)).unwrap()
+}
+
+#[test]
+fn prints_account_json() {
+    assert_eq!(
+        run_json("address", &["--json"]),
+        json!({ "address": "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1" }),
+    );
+    assert_eq!(
+        run_json("export", &["--json"]),
+        json!({
+            "privateKey": "0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d",
+        }),
+    );
+    assert_eq!(
+        run_json("public-key", &["--json", "--compressed"]),
+        json!({
+            "publicKey": "0x03e68acfc0253a10620dff706b0a1b1f1f5833ea3beb3bde2250d5f271f3563606",
+        }),
+    );
+}
+
+#[test]
+fn prints_signature_json() {
+    assert_eq!(
+        run_json(
+            "sign",
+            &[
+                "--json",
+                "raw",
+                "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+            ],
+        ),
+        json!({
+            "signature": "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84\
+                            61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859\
+                            1c",
+            "r": "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84",
+            "s": "0x61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859",
+            "v": 28,
+        }),
+    );
+}
+
+#[test]
+fn prints_labeled_hash_json() {
+    let output = Hdwallet::new("hash", &["message", "-", "Cargo.toml", "--json"])
+        .stdin("Hello World!")
+        .execute()
+        .unwrap();
+    let hashes = output
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(hashes.len(), 2);
+    assert_eq!(
+        hashes[0],
+        json!({
+            "path": "-",
+            "hash": "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+        }),
+    );
+    assert_eq!(hashes[1]["path"], "Cargo.toml");
+}
+
+#[test]
+fn prints_recovered_signer_json() {
+    let output = Hdwallet::new(
+        "recover",
+        &[
+            "--json",
+            "--signature",
+            "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84\
+               61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859\
+               1c",
+            "message",
+            "-",
+        ],
+    )
+    .stdin("Hello World!")
+    .execute()
+    .unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(&output).unwrap(),
+        json!({ "address": "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1" }),
+    );
+}
+
+#[test]
+fn rejects_json_for_unsupported_subcommands() {
+    for (subcommand, args) in [
+        ("hex", &["encode", "-"][..]),
+        ("mnemonic", &["expand", "aban aban aban"]),
+        ("new", &[]),
+    ] {
+        let err = Hdwallet::new(subcommand, &[&["--json"][..], args].concat())
+            .stdin("")
+            .execute()
+            .unwrap_err();
+        assert!(err.contains("'--json' option is not supported"), "{err}");
+    }
+}
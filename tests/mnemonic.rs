@@ -0,0 +1,53 @@
+mod util;
+
+use hdwallet::mnemonic::Mnemonic;
+use util::Hdwallet;
+
+#[test]
+fn computes_valid_last_words() {
+    let partial = "myth like bonus scare over problem client lizard pioneer submit female";
+    let words = Hdwallet::run("mnemonic", &["last-words", partial]);
+
+    let words = words.lines().collect::<Vec<_>>();
+    assert_eq!(words.len(), 128);
+    assert!(words.contains(&"collect"));
+    for word in words {
+        assert!(Mnemonic::from_phrase(format!("{partial} {word}")).is_ok());
+    }
+}
+
+#[test]
+fn errors_on_invalid_partial_length() {
+    assert!(
+        Hdwallet::new("mnemonic", &["last-words", "abandon abandon"])
+            .execute()
+            .is_err()
+    );
+}
+
+#[test]
+fn expands_abbreviated_phrase() {
+    let phrase = Hdwallet::run(
+        "mnemonic",
+        &[
+            "expand",
+            "myth like bonu scar over prob clie liza pion subm fema coll",
+        ],
+    );
+    assert_eq!(phrase, util::DETERMINISTIC_MNEMONIC);
+    assert!(Mnemonic::from_phrase(&phrase).is_ok());
+}
+
+#[test]
+fn errors_on_ambiguous_abbreviation() {
+    let err = Hdwallet::new(
+        "mnemonic",
+        &[
+            "expand",
+            "my like bonu scar over prob clie liza pion subm fema coll",
+        ],
+    )
+    .execute()
+    .unwrap_err();
+    assert!(err.contains("ambiguous"));
+}
@@ -0,0 +1,33 @@
+mod util;
+
+use crate::util::{Hdwallet, DETERMINISTIC_MNEMONIC};
+use std::{env, fs, process};
+
+#[test]
+fn reads_mnemonic_from_file() {
+    let path = env::temp_dir().join(format!("hdwallet-mnemonic-{}", process::id()));
+    fs::write(&path, format!("  {DETERMINISTIC_MNEMONIC}\n")).unwrap();
+
+    let path = path.to_str().unwrap();
+    let address = Hdwallet::new("address", &["--mnemonic-file", path])
+        .env_remove("MNEMONIC")
+        .execute();
+    let precedence = Hdwallet::new("address", &["--mnemonic-file", path])
+        .env(
+            "MNEMONIC",
+            "abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon about",
+        )
+        .execute();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(
+        address.unwrap(),
+        "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1"
+    );
+    // The mnemonic file takes precedence over the environment.
+    assert_eq!(
+        precedence.unwrap(),
+        "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1"
+    );
+}
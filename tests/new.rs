@@ -21,3 +21,174 @@ fn errors_on_invalid_length() {
     assert!(Hdwallet::new("new", &["--length", "1"]).execute().is_err());
     assert!(Hdwallet::new("new", &["--length", "42"]).execute().is_err());
 }
+
+#[test]
+fn generates_mnemonic_from_entropy_file() {
+    let mnemonic = Hdwallet::new("new", &["--entropy-file", "-"])
+        .stdin([0; 16])
+        .execute()
+        .unwrap();
+    assert_eq!(
+        mnemonic,
+        "abandon abandon abandon abandon abandon abandon \
+         abandon abandon abandon abandon abandon about",
+    );
+
+    let mnemonic = Hdwallet::new("new", &["--entropy-file", "-", "--length", "24"])
+        .stdin([0; 32])
+        .execute()
+        .unwrap();
+    assert_eq!(
+        mnemonic,
+        "abandon abandon abandon abandon abandon abandon \
+         abandon abandon abandon abandon abandon abandon \
+         abandon abandon abandon abandon abandon abandon \
+         abandon abandon abandon abandon abandon art",
+    );
+}
+
+#[test]
+fn errors_on_entropy_length_mismatch() {
+    assert!(Hdwallet::new("new", &["--entropy-file", "-"])
+        .stdin([0; 32])
+        .execute()
+        .is_err());
+    assert!(Hdwallet::new("new", &["--entropy-file", "-"])
+        .stdin([0; 15])
+        .execute()
+        .is_err());
+}
+
+#[cfg(feature = "deterministic-rand")]
+#[test]
+fn generates_deterministic_mnemonic_from_test_seed() {
+    let mnemonic = Hdwallet::new("new", &[])
+        .env("HDWALLET_TEST_SEED", "hdwallet")
+        .execute()
+        .unwrap();
+    assert_eq!(
+        mnemonic,
+        "mansion avoid potato there bag drastic \
+         embody ladder ginger flight dolphin slide",
+    );
+}
+
+#[test]
+fn prints_matching_vanity_address() {
+    let (mnemonic, stderr) = Hdwallet::new("new", &["--vanity-prefix", "0x0"])
+        .execute_with_stderr()
+        .unwrap();
+
+    let address = Hdwallet::new("address", &[])
+        .env("MNEMONIC", &mnemonic)
+        .execute()
+        .unwrap();
+    assert!(address.starts_with("0x0"));
+    assert!(stderr.contains(&format!(
+        "found vanity address {address} at m/44'/60'/0'/0/0"
+    )));
+}
+
+#[test]
+fn searches_vanity_address_in_parallel() {
+    for jobs in ["1", "4"] {
+        let mnemonic = Hdwallet::run("new", &["--vanity-prefix", "0x00", "--jobs", jobs]);
+        let address = Hdwallet::new("address", &[])
+            .env("MNEMONIC", &mnemonic)
+            .execute()
+            .unwrap();
+        assert!(address.starts_with("0x00"));
+    }
+
+    assert!(Hdwallet::new("new", &["--jobs", "2"]).execute().is_err());
+    assert!(
+        Hdwallet::new("new", &["--vanity-prefix", "0x0", "--jobs", "0"])
+            .execute()
+            .is_err()
+    );
+}
+
+#[cfg(feature = "deterministic-rand")]
+#[test]
+fn searches_vanity_address_deterministically_with_single_job() {
+    let search = || {
+        Hdwallet::new("new", &["--vanity-prefix", "0x0", "--jobs", "1"])
+            .env("HDWALLET_TEST_SEED", "hdwallet")
+            .execute()
+            .unwrap()
+    };
+    assert_eq!(search(), search());
+}
+
+#[test]
+fn prints_matching_vanity_suffix_address() {
+    let address_for = |mnemonic: &str| {
+        Hdwallet::new("address", &[])
+            .env("MNEMONIC", mnemonic)
+            .execute()
+            .unwrap()
+    };
+
+    let mnemonic = Hdwallet::run("new", &["--vanity-suffix", "0xf"]);
+    assert!(address_for(&mnemonic).to_lowercase().ends_with('f'));
+
+    let mnemonic = Hdwallet::run("new", &["--vanity-prefix", "0x1", "--vanity-suffix", "0x2"]);
+    let address = address_for(&mnemonic);
+    assert!(address.starts_with("0x1") && address.ends_with('2'));
+}
+
+#[test]
+fn prints_matching_vanity_checksum_address() {
+    let mnemonic = Hdwallet::run("new", &["--vanity-prefix", "0xA", "--vanity-checksum"]);
+    let address = Hdwallet::new("address", &[])
+        .env("MNEMONIC", &mnemonic)
+        .execute()
+        .unwrap();
+    assert!(address.starts_with("0xA"));
+
+    assert!(Hdwallet::new("new", &["--vanity-checksum"])
+        .execute()
+        .is_err());
+}
+
+#[test]
+fn rejects_impossible_vanity_patterns() {
+    let too_long = format!("0x{}", "0".repeat(41));
+    let err = Hdwallet::new("new", &["--vanity-prefix", &too_long])
+        .execute()
+        .unwrap_err();
+    assert!(err.contains("more than 40 hex digits"), "{err}");
+    assert!(Hdwallet::new("new", &["--vanity-suffix", &too_long])
+        .execute()
+        .is_err());
+
+    let (prefix, suffix) = (
+        format!("0x{}", "1".repeat(30)),
+        format!("0x{}", "2".repeat(20)),
+    );
+    let err = Hdwallet::new(
+        "new",
+        &["--vanity-prefix", &prefix, "--vanity-suffix", &suffix],
+    )
+    .execute()
+    .unwrap_err();
+    assert!(err.contains("overlap with conflicting digits"), "{err}");
+
+    let (prefix, suffix) = (
+        format!("0x{}", "a".repeat(30)),
+        format!("0x{}", "A".repeat(20)),
+    );
+    let err = Hdwallet::new(
+        "new",
+        &[
+            "--vanity-prefix",
+            &prefix,
+            "--vanity-suffix",
+            &suffix,
+            "--vanity-checksum",
+        ],
+    )
+    .execute()
+    .unwrap_err();
+    assert!(err.contains("overlap with conflicting digits"), "{err}");
+}
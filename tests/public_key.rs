@@ -0,0 +1,16 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+#[test]
+fn prints_public_key() {
+    assert_eq!(
+        Hdwallet::run("public-key", &[]),
+        "0x04e68acfc0253a10620dff706b0a1b1f1f5833ea3beb3bde2250d5f271f3563606\
+           672ebc45e0b7ea2e816ecb70ca03137b1c9476eec63d4632e990020b7b6fba39",
+    );
+    assert_eq!(
+        Hdwallet::run("public-key", &["--compressed"]),
+        "0x03e68acfc0253a10620dff706b0a1b1f1f5833ea3beb3bde2250d5f271f3563606",
+    );
+}
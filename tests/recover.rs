@@ -0,0 +1,36 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+/// The signature of `keccak256("\x19Ethereum Signed Message:\n12Hello World!")`
+/// with the Ganache deterministic account.
+const SIGNATURE: &str = "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84\
+                           61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859\
+                           1c";
+
+#[test]
+fn recovers_message_signer() {
+    let signer = Hdwallet::new("recover", &["--signature", SIGNATURE, "message", "-"])
+        .stdin("Hello World!")
+        .execute()
+        .unwrap();
+    assert_eq!(signer, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+
+    let signer = Hdwallet::new(
+        "recover",
+        &["--signature", SIGNATURE, "message", "--hex", "-"],
+    )
+    .stdin("0x48656c6c6f20576f726c6421")
+    .execute()
+    .unwrap();
+    assert_eq!(signer, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+}
+
+#[test]
+fn recovers_different_signer_for_other_message() {
+    let signer = Hdwallet::new("recover", &["--signature", SIGNATURE, "message", "-"])
+        .stdin("Hello Bob!")
+        .execute()
+        .unwrap();
+    assert_ne!(signer, "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+}
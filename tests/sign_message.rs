@@ -0,0 +1,54 @@
+mod util;
+
+use crate::util::Hdwallet;
+use std::{env, fs, process};
+
+/// The signature of `keccak256("\x19Ethereum Signed Message:\n12Hello World!")`
+/// with the Ganache deterministic account.
+const SIGNATURE: &str = "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84\
+                           61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859\
+                           1c";
+
+#[test]
+fn signs_multiple_message_files() {
+    let dir = env::temp_dir().join(format!("hdwallet-sign-message-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let messages = ["Hello World!", "Hello Bob!", "Hello Cow!"];
+    let paths = messages
+        .iter()
+        .enumerate()
+        .map(|(i, message)| {
+            let path = dir.join(format!("message{i}.txt"));
+            fs::write(&path, message).unwrap();
+            path.to_str().unwrap().to_owned()
+        })
+        .collect::<Vec<_>>();
+
+    let args = ["message"]
+        .into_iter()
+        .chain(paths.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    let output = Hdwallet::run("sign", &args);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], format!("{}: {SIGNATURE}", paths[0]));
+    for ((line, path), message) in lines.iter().zip(&paths).zip(messages) {
+        let signature = Hdwallet::new("sign", &["message", "-"])
+            .stdin(message)
+            .execute()
+            .unwrap();
+        assert_eq!(*line, format!("{path}: {signature}"));
+    }
+}
+
+#[test]
+fn signs_hex_encoded_message() {
+    let signature = Hdwallet::new("sign", &["message", "--hex", "-"])
+        .stdin("0x48656c6c6f20576f726c6421\n")
+        .execute()
+        .unwrap();
+    assert_eq!(signature, SIGNATURE);
+}
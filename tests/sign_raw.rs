@@ -0,0 +1,135 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+/// The signature of `keccak256("\x19Ethereum Signed Message:\n12Hello World!")`
+/// with the Ganache deterministic account.
+const SIGNATURE: &str = "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84\
+                           61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859\
+                           1c";
+
+#[test]
+fn signs_raw_digest_argument() {
+    let signature = Hdwallet::run(
+        "sign",
+        &[
+            "raw",
+            "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+        ],
+    );
+    assert_eq!(signature, SIGNATURE);
+}
+
+#[test]
+fn signs_raw_digest_from_stdin() {
+    let signature = Hdwallet::new("sign", &["raw", "-"])
+        .stdin("0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c\n")
+        .execute()
+        .unwrap();
+    assert_eq!(signature, SIGNATURE);
+}
+
+#[test]
+fn signs_raw_bytes_from_stdin() {
+    let digest =
+        hex::decode("ec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c").unwrap();
+    let signature = Hdwallet::new("sign", &["raw", "--binary", "-"])
+        .stdin(digest.clone())
+        .execute()
+        .unwrap();
+    assert_eq!(signature, SIGNATURE);
+
+    // Raw bytes are never accepted without `--binary`.
+    assert!(Hdwallet::new("sign", &["raw", "-"])
+        .stdin(digest)
+        .execute()
+        .is_err());
+}
+
+#[test]
+fn does_not_sign_hex_string_as_raw_bytes() {
+    // A 32 character hexadecimal string is exactly 32 bytes long, but must
+    // never be signed as its ASCII bytes.
+    let hex_string = "ec3608877ecbf8084c29896b7eab2a36";
+    assert!(Hdwallet::new("sign", &["raw", "-"])
+        .stdin(hex_string)
+        .execute()
+        .is_err());
+    assert!(Hdwallet::new("sign", &["raw", "--binary", "-"])
+        .stdin("0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c")
+        .execute()
+        .is_err());
+    assert!(Hdwallet::new(
+        "sign",
+        &[
+            "raw",
+            "--binary",
+            "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+        ],
+    )
+    .execute()
+    .is_err());
+}
+
+#[test]
+fn errors_on_invalid_digest_length_from_stdin() {
+    assert!(Hdwallet::new("sign", &["raw", "-"])
+        .stdin("0x0102")
+        .execute()
+        .is_err());
+}
+
+#[test]
+fn signs_with_v_format() {
+    for (args, v) in [
+        (&["--v-format", "legacy"][..], "1c"),
+        (&["--v-format", "raw"][..], "01"),
+        (&["--v-format", "eip155", "--chain-id", "1"][..], "26"),
+        (&["--v-format", "eip155", "--chain-id", "100"][..], "ec"),
+    ] {
+        let signature = Hdwallet::run(
+            "sign",
+            &[
+                &[
+                    "raw",
+                    "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+                ][..],
+                args,
+            ]
+            .concat(),
+        );
+        assert_eq!(signature, format!("{}{v}", &SIGNATURE[..130]));
+    }
+}
+
+#[test]
+fn errors_on_eip155_v_format_without_chain_id() {
+    assert!(Hdwallet::new(
+        "sign",
+        &[
+            "raw",
+            "--v-format",
+            "eip155",
+            "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+        ],
+    )
+    .execute()
+    .is_err());
+}
+
+#[test]
+fn errors_on_eip155_v_overflowing_a_byte() {
+    assert!(Hdwallet::new(
+        "sign",
+        &[
+            "raw",
+            "--v-format",
+            "eip155",
+            "--chain-id",
+            "110",
+            "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+        ],
+    )
+    .execute()
+    .is_err());
+}
@@ -0,0 +1,160 @@
+mod util;
+
+use crate::util::Hdwallet;
+use hdwallet::transaction::Transaction;
+
+const ZERO_ADDRESS_TRANSFER: &str = r#"{
+    "chainId": 1,
+    "nonce": 0,
+    "maxPriorityFeePerGas": 1e9,
+    "maxFeePerGas": 42e9,
+    "gas": 21000,
+    "to": "0x0000000000000000000000000000000000000000",
+    "value": 1e18,
+    "data": "0x"
+}"#;
+
+#[test]
+fn errors_on_zero_address_transfer() {
+    let err = Hdwallet::new("sign", &["transaction", "-"])
+        .stdin(ZERO_ADDRESS_TRANSFER)
+        .execute()
+        .unwrap_err();
+    assert!(err.contains("--allow-burn"));
+}
+
+#[test]
+fn allows_zero_address_transfer_with_flag() {
+    assert!(Hdwallet::new("sign", &["transaction", "--allow-burn", "-"])
+        .stdin(ZERO_ADDRESS_TRANSFER)
+        .execute()
+        .is_ok());
+}
+
+#[test]
+fn signs_contract_creation_with_value() {
+    assert!(Hdwallet::new("sign", &["transaction", "-"])
+        .stdin(
+            r#"{
+                "chainId": 1,
+                "nonce": 0,
+                "maxPriorityFeePerGas": 1e9,
+                "maxFeePerGas": 42e9,
+                "gas": 100000,
+                "value": 1e18,
+                "data": "0x6080604052"
+            }"#,
+        )
+        .execute()
+        .is_ok());
+}
+
+#[test]
+fn signs_with_account_index() {
+    let signer = Hdwallet::run("address", &["--account-index", "1"]);
+    assert_eq!(signer, "0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0");
+
+    let (encoded, stderr) = Hdwallet::new(
+        "sign",
+        &["--account-index", "1", "transaction", "--verbose", "-"],
+    )
+    .stdin(
+        r#"{
+            "chainId": 1,
+            "nonce": 0,
+            "maxPriorityFeePerGas": 1e9,
+            "maxFeePerGas": 42e9,
+            "gas": 21000,
+            "to": "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1",
+            "value": 1e18,
+            "data": "0x"
+        }"#,
+    )
+    .execute_with_stderr()
+    .unwrap();
+    assert!(
+        stderr.contains("signed by 0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0 (m/44'/60'/0'/0/1)")
+    );
+
+    let (tx, signature) =
+        Transaction::decode(&hex::decode(encoded.strip_prefix("0x").unwrap()).unwrap()).unwrap();
+    assert_eq!(tx.recover_signer(&signature).unwrap().to_string(), signer);
+}
+
+#[test]
+fn errors_on_zero_chain_id() {
+    let transaction = r#"{
+        "chainId": 0,
+        "nonce": 0,
+        "maxPriorityFeePerGas": 1e9,
+        "maxFeePerGas": 42e9,
+        "gas": 21000,
+        "to": "0x1111111111111111111111111111111111111111",
+        "value": 1e18,
+        "data": "0x"
+    }"#;
+
+    let err = Hdwallet::new("sign", &["transaction", "-"])
+        .stdin(transaction)
+        .execute()
+        .unwrap_err();
+    assert!(err.contains("--allow-zero-chain-id"));
+
+    assert!(
+        Hdwallet::new("sign", &["transaction", "--allow-zero-chain-id", "-"])
+            .stdin(transaction)
+            .execute()
+            .is_ok()
+    );
+}
+
+#[test]
+fn errors_on_gas_below_intrinsic_gas() {
+    let transaction = r#"{
+        "chainId": 1,
+        "nonce": 0,
+        "maxPriorityFeePerGas": 1e9,
+        "maxFeePerGas": 42e9,
+        "gas": 2100,
+        "to": "0x1111111111111111111111111111111111111111",
+        "value": 1e18,
+        "data": "0x"
+    }"#;
+
+    let err = Hdwallet::new("sign", &["transaction", "-"])
+        .stdin(transaction)
+        .execute()
+        .unwrap_err();
+    assert!(err.contains("--allow-low-gas"));
+
+    assert!(
+        Hdwallet::new("sign", &["transaction", "--allow-low-gas", "-"])
+            .stdin(transaction)
+            .execute()
+            .is_ok()
+    );
+}
+
+#[test]
+fn signs_json5_transaction() {
+    let transaction = r#"{
+        "chainId": 1,
+        "nonce": 0,
+        "maxPriorityFeePerGas": 1e9, // 1 Gwei
+        "maxFeePerGas": 42e9,
+        "gas": 21000,
+        /* Send to the second account. */
+        "to": "0xFFcf8FDEE72ac11b5c542428B35EEF5769C409f0",
+        "value": 1e18,
+        "data": "0x",
+    }"#;
+
+    assert!(Hdwallet::new("sign", &["transaction", "-"])
+        .stdin(transaction)
+        .execute()
+        .is_err());
+    assert!(Hdwallet::new("sign", &["transaction", "--json5", "-"])
+        .stdin(transaction)
+        .execute()
+        .is_ok());
+}
@@ -0,0 +1,142 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+fn validate(transaction: &str) -> Result<String, String> {
+    Hdwallet::new("transaction", &["validate", "-"])
+        .stdin(transaction)
+        .execute()
+}
+
+fn validation_errors(transaction: &str) -> String {
+    let (report, err) = Hdwallet::new("transaction", &["validate", "-"])
+        .stdin(transaction)
+        .execute_failure();
+    assert!(err.contains("failed validation"), "{err}");
+    report
+}
+
+#[test]
+fn validates_clean_transaction() {
+    let report = validate(
+        r#"{
+            "chainId": 1,
+            "nonce": 0,
+            "maxPriorityFeePerGas": 1e9,
+            "maxFeePerGas": 42e9,
+            "gas": 21000,
+            "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+            "value": 0,
+            "data": "0x"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(report, "");
+}
+
+#[test]
+fn warns_on_missing_chain_id() {
+    let report = validate(
+        r#"{
+            "nonce": 0,
+            "gasPrice": 42e9,
+            "gas": 21000,
+            "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+            "value": 0,
+            "data": "0x"
+        }"#,
+    )
+    .unwrap();
+    assert!(report.contains("without chain ID"));
+}
+
+#[test]
+fn warns_on_zero_address_transfer() {
+    let report = validate(
+        r#"{
+            "chainId": 1,
+            "nonce": 0,
+            "gasPrice": 42e9,
+            "gas": 21000,
+            "to": "0x0000000000000000000000000000000000000000",
+            "value": 1e18,
+            "data": "0x",
+            "accessList": []
+        }"#,
+    )
+    .unwrap();
+    assert!(report.contains("zero address"));
+}
+
+#[test]
+fn warns_on_non_checksummed_address() {
+    let report = validate(
+        r#"{
+            "chainId": 1,
+            "nonce": 0,
+            "gasPrice": 42e9,
+            "gas": 21000,
+            "to": "0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+            "value": 0,
+            "data": "0x",
+            "accessList": []
+        }"#,
+    )
+    .unwrap();
+    assert!(report.contains("not EIP-55 checksummed"));
+}
+
+#[test]
+fn errors_on_invalid_checksum() {
+    assert!(validate(
+        r#"{
+            "chainId": 1,
+            "nonce": 0,
+            "gasPrice": 42e9,
+            "gas": 21000,
+            "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeef",
+            "value": 0,
+            "data": "0x",
+            "accessList": []
+        }"#,
+    )
+    .is_err());
+}
+
+#[test]
+fn errors_on_insufficient_gas() {
+    let report = validation_errors(
+        r#"{
+            "chainId": 1,
+            "nonce": 0,
+            "gasPrice": 42e9,
+            "gas": 21000,
+            "value": 0,
+            "data": "0x6080604052"
+        }"#,
+    );
+    assert_eq!(
+        report,
+        "error: gas limit 21000 is below the intrinsic gas 53082",
+    );
+}
+
+#[test]
+fn errors_on_priority_fee_above_max_fee() {
+    let report = validation_errors(
+        r#"{
+            "chainId": 1,
+            "nonce": 0,
+            "maxPriorityFeePerGas": 42e9,
+            "maxFeePerGas": 1e9,
+            "gas": 21000,
+            "to": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+            "value": 0,
+            "data": "0x"
+        }"#,
+    );
+    assert_eq!(
+        report,
+        "error: max priority fee per gas 42000000000 exceeds max fee per gas 1000000000",
+    );
+}
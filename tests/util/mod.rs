@@ -1,4 +1,4 @@
-#![allow(dead_code)]
+#![allow(dead_code, unused_imports)]
 
 #[path = "../../src/ganache.rs"]
 mod ganache;
@@ -6,7 +6,7 @@ mod ganache;
 pub use ganache::*;
 use std::{
     io::Write as _,
-    process::{Command, Stdio},
+    process::{Command, Output, Stdio},
     str, thread,
 };
 
@@ -22,14 +22,28 @@ impl Hdwallet {
         let mut command = Command::new("cargo");
         command
             .env("MNEMONIC", ganache::DETERMINISTIC_MNEMONIC)
-            .args(["run", "--", subcommand])
-            .args(args);
+            .arg("run");
+        #[cfg(feature = "deterministic-rand")]
+        command.args(["--features", "deterministic-rand"]);
+        command.args(["--", subcommand]).args(args);
         Self {
             command,
             stdin: None,
         }
     }
 
+    /// Sets an environment variable for the command.
+    pub fn env(mut self, key: &str, value: &str) -> Self {
+        self.command.env(key, value);
+        self
+    }
+
+    /// Removes an environment variable for the command.
+    pub fn env_remove(mut self, key: &str) -> Self {
+        self.command.env_remove(key);
+        self
+    }
+
     /// Sets the standard input to be used for the command.
     pub fn stdin(mut self, data: impl Into<Vec<u8>>) -> Self {
         self.stdin = Some(data.into());
@@ -38,7 +52,36 @@ impl Hdwallet {
 
     /// Executes the command and returns the standard output on success and
     /// standard error on failure.
-    pub fn execute(mut self) -> Result<String, String> {
+    pub fn execute(self) -> Result<String, String> {
+        self.execute_with_stderr().map(|(stdout, _)| stdout)
+    }
+
+    /// Executes the command and returns both the standard output and standard
+    /// error on success and standard error on failure.
+    pub fn execute_with_stderr(self) -> Result<(String, String), String> {
+        let output = self.output();
+        if output.status.success() {
+            Ok((
+                string_from_utf8(output.stdout),
+                string_from_utf8(output.stderr),
+            ))
+        } else {
+            Err(string_from_utf8(output.stderr))
+        }
+    }
+
+    /// Executes the command, expecting it to fail, and returns both the
+    /// standard output and standard error.
+    pub fn execute_failure(self) -> (String, String) {
+        let output = self.output();
+        assert!(!output.status.success(), "command unexpectedly succeeded");
+        (
+            string_from_utf8(output.stdout),
+            string_from_utf8(output.stderr),
+        )
+    }
+
+    fn output(mut self) -> Output {
         self.command.stdout(Stdio::piped()).stderr(Stdio::piped());
         if self.stdin.is_some() {
             self.command.stdin(Stdio::piped());
@@ -57,12 +100,7 @@ impl Hdwallet {
         if let Some(input) = input {
             input.join().unwrap();
         }
-
-        if output.status.success() {
-            Ok(string_from_utf8(output.stdout))
-        } else {
-            Err(string_from_utf8(output.stderr))
-        }
+        output
     }
 
     /// Builds and executes an `hdwallet` command.
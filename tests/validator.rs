@@ -0,0 +1,23 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+const VALIDATOR: &str = "0x1111111111111111111111111111111111111111";
+
+#[test]
+fn hashes_and_signs_validator_message() {
+    let hash = Hdwallet::new("hash", &["validator", "--validator", VALIDATOR, "-"])
+        .stdin("Hello World!")
+        .execute()
+        .unwrap();
+    assert_eq!(
+        hash,
+        "0xc1bbf904da8ae156efd55334bb52f4a7e0e218566db96446d2f3dee1972dad02",
+    );
+
+    let signature = Hdwallet::new("sign", &["validator", "--validator", VALIDATOR, "-"])
+        .stdin("Hello World!")
+        .execute()
+        .unwrap();
+    assert_eq!(signature, Hdwallet::run("sign", &["raw", &hash]));
+}
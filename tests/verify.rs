@@ -0,0 +1,66 @@
+mod util;
+
+use crate::util::Hdwallet;
+
+/// The signature of `keccak256("\x19Ethereum Signed Message:\n12Hello World!")`
+/// with the Ganache deterministic account.
+const SIGNATURE: &str = "0x408790f153cbfa2722fc708a57d97a43b24429724cf060df7c915d468c43bd84\
+                           61c96aac95ce37d7a31087b6634f4a3ea439a9f704b5c818584fa2a32fa83859\
+                           1c";
+
+/// The Ganache deterministic account address.
+const ADDRESS: &str = "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1";
+
+#[test]
+fn verifies_message_signature() {
+    let signer = Hdwallet::new(
+        "verify",
+        &[
+            "--signature",
+            SIGNATURE,
+            "--address",
+            ADDRESS,
+            "message",
+            "-",
+        ],
+    )
+    .stdin("Hello World!")
+    .execute()
+    .unwrap();
+    assert_eq!(signer, ADDRESS);
+}
+
+#[test]
+fn verifies_raw_signature() {
+    let signer = Hdwallet::run(
+        "verify",
+        &[
+            "--signature",
+            SIGNATURE,
+            "--address",
+            ADDRESS,
+            "raw",
+            "0xec3608877ecbf8084c29896b7eab2a368b2b3c8d003288584d145613dfa4706c",
+        ],
+    );
+    assert_eq!(signer, ADDRESS);
+}
+
+#[test]
+fn errors_on_signer_mismatch() {
+    let err = Hdwallet::new(
+        "verify",
+        &[
+            "--signature",
+            SIGNATURE,
+            "--address",
+            "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+            "message",
+            "-",
+        ],
+    )
+    .stdin("Goodbye World!")
+    .execute()
+    .unwrap_err();
+    assert!(err.contains("signed by"));
+}